@@ -2,8 +2,6 @@
 // Make me compile and pass the test!
 // Execute `starklings hint arrays3` or use the `hint` watch subcommand for a hint.
 
-// I AM NOT DONE
-
 use array::ArrayTrait;
 use option::OptionTrait;
 
@@ -1,8 +1,6 @@
 // enums1.cairo
 // No hints this time! ;)
 
-// I AM NOT DONE
-
 use debug::print;
 use debug::PrintTrait;
 enum Message { // TODO: define a few types of messages as used below
@@ -1,8 +1,6 @@
 // functions1.cairo
 // Execute `starklings hint functions1` or use the `hint` watch subcommand for a hint.
 
-// I AM NOT DONE
-
 fn main() {
     call_me();
 }
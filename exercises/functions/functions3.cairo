@@ -1,7 +1,6 @@
 // functions3.cairo
 // Execute `starklings hint functions3` or use the `hint` watch subcommand for a hint.
 
-// I AM NOT DONE
 use debug::PrintTrait;
 
 fn main() {
@@ -7,7 +7,6 @@
 // in the signatures for now. If anything, this is a good way to peek ahead
 // to future exercises!)
 
-// I AM NOT DONE
 use debug::PrintTrait;
 
 fn main() {
@@ -1,2 +1 @@
-// I AM NOT DONE
 // This exercise won't compile... Can you make it compile?
@@ -1,4 +1,3 @@
-// I AM NOT DONE
 // Cairo is a typed language. A popular type is `felt252`, which is like a number.
 // The function below is missing some types and there are a couple errors. Can you see fix this?
 
@@ -1,4 +1,3 @@
-// I AM NOT DONE
 // Remember add function from before? The `add` function below does the exact same
 // thing! If you skip out a `semicolon` at the end of the function, the function 
 // returns the result of that statement. Pretty neat, eh?
@@ -5,8 +5,6 @@
 use array::ArrayTrait;
 use debug::PrintTrait;
 
-// I AM NOT DONE
-
 fn main() {
     let arr0 = ArrayTrait::new();
 
@@ -3,8 +3,6 @@
 // (no lines with multiple semicolons necessary!)
 // Execute `starklings hint move_semantics3` or use the `hint` watch subcommand for a hint.
 
-// I AM NOT DONE
-
 use array::ArrayTrait;
 use array::ArrayTCloneImpl;
 use array::SpanTrait;
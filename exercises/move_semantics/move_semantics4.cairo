@@ -4,7 +4,6 @@
 // function.
 // Execute `starklings hint move_semantics4` or use the `hint` watch subcommand for a hint.
 
-// I AM NOT DONE
 use array::ArrayTrait;
 use array::ArrayTCloneImpl;
 use array::SpanTrait;
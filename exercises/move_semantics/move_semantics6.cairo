@@ -2,7 +2,6 @@
 // Execute `starklings hint move_semantics6` or use the `hint` watch subcommand for a hint.
 // You can't change anything except adding or removing references.
 
-// I AM NOT DONE
 use debug::PrintTrait;
 
 #[derive(Drop)]
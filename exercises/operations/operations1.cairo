@@ -1,8 +1,6 @@
 // Integer types implement basic comparison and arithmetic operators.
 // Felt252 operations should be avoided where possible, as they could have unwanted behavior.
 
-// I AM NOT DONE
-
 // TODO
 // Return the solution of x^3 + y - 2
 
@@ -3,8 +3,6 @@
 // And always watch out for overflows e.g in the last test
 // Let try to use them
 
-// I AM NOT DONE
-
 
 fn modulus(x : u8, y: u8) ->  u8 {
     // calculate the modulus of x and y
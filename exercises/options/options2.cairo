@@ -1,8 +1,6 @@
 // options2.cairo
 // Execute `starklings hint options2` or use the `hint` watch subcommand for a hint.
 
-// I AM NOT DONE
-
 use option::OptionTrait;
 use debug::PrintTrait;
 
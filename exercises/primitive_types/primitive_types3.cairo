@@ -2,8 +2,6 @@
 // Destructure the `cat` tuple to call print on each member.
 // Execute `starklings hint primitive_types3` or use the `hint` watch subcommand for a hint.
 
-// I AM NOT DONE
-
 use debug::PrintTrait;
 
 fn main() {
@@ -2,7 +2,6 @@
 // Address all the TODOs to make the tests pass!
 // Execute `starklings hint structs1` or use the `hint` watch subcommand for a hint.
 
-// I AM NOT DONE
 #[derive(Copy, Drop)]
 struct ColorStruct {
     // TODO: Something goes here
@@ -8,8 +8,6 @@
 
 // Fill in the impl block to make the code work.
 
-// I AM NOT DONE
-
 #[derive(Copy, Drop)]
 struct Animal {
     noise: felt252
@@ -13,8 +13,6 @@
 // No hints for this one!
 // Execute `starklings hint traits2` or use the `hint` watch subcommand for a hint.
 
-// I AM NOT DONE
-
 #[derive(Copy, Drop)]
 struct Cat {
     noise: felt252,
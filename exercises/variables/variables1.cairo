@@ -2,8 +2,6 @@
 // Make me compile!
 // Execute `starklings hint variables1` or use the `hint` watch subcommand for a hint.
 
-// I AM NOT DONE
-
 use debug::PrintTrait;
 
 fn main() {
@@ -1,7 +1,6 @@
 // variables2.cairo
 // Execute `starklings hint variables2` or use the `hint` watch subcommand for a hint.
 
-// I AM NOT DONE
 use debug::PrintTrait;
 
 fn main() {
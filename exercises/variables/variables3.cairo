@@ -1,7 +1,6 @@
 // variables3.cairo
 // Execute `starklings hint variables3` or use the `hint` watch subcommand for a hint.
 
-// I AM NOT DONE
 use debug::PrintTrait;
 
 fn main() {
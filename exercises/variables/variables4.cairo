@@ -1,7 +1,6 @@
 // variables4.cairo
 // Execute `starklings hint variables4` or use the `hint` watch subcommand for a hint.
 
-// I AM NOT DONE
 use debug::PrintTrait;
 
 fn main() {
@@ -1,7 +1,6 @@
 // variables6.cairo
 // Execute `starklings hint variables6` or use the `hint` watch subcommand for a hint.
 
-// I AM NOT DONE
 use debug::PrintTrait;
 
 const NUMBER = 3;
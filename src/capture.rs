@@ -0,0 +1,87 @@
+//! Captures what Cairo programs print.
+//! The `print` hints of the Cairo runner write straight to the process stdout,
+//! so it is redirected to a buffer while the program runs.
+
+use std::fmt::{self, Display, Formatter};
+use std::io::{self, Read, Write};
+
+use gag::BufferRedirect;
+use serde::Serialize;
+
+/// Calls `f`, capturing what it prints to stdout.
+/// Only one redirect may exist at a time, so the output is not captured,
+/// and `None` is returned instead, when stdout is already being captured.
+pub fn capture_stdout<T>(f: impl FnOnce() -> T) -> (T, Option<String>) {
+    let _ = io::stdout().flush();
+    let Ok(mut redirect) = BufferRedirect::stdout() else {
+        return (f(), None);
+    };
+    let value = f();
+    let _ = io::stdout().flush();
+    let mut output = String::new();
+    let captured = redirect.read_to_string(&mut output).is_ok();
+    (value, captured.then_some(output))
+}
+
+/// A felt printed with `debug::print`.
+#[derive(Serialize, Clone, Debug, PartialEq, Eq)]
+pub struct PrintedFelt {
+    /// The felt, in decimal.
+    pub value: String,
+    /// The felt decoded as a short string, if it is one.
+    pub text: Option<String>,
+}
+
+impl Display for PrintedFelt {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match &self.text {
+            Some(text) => write!(f, "{text}"),
+            None => write!(f, "{}", self.value),
+        }
+    }
+}
+
+/// Parses the felts printed by a Cairo program from the runner output.
+/// The runner writes every felt on its own line, as
+/// `[DEBUG]\t<short string>\t(raw: <felt>)`.
+pub fn parse_printed(output: &str) -> Vec<PrintedFelt> {
+    output
+        .lines()
+        .filter_map(|line| {
+            let line = line.strip_prefix("[DEBUG]")?;
+            let (text, raw) = line.rsplit_once("(raw:")?;
+            let value = raw.trim().trim_end_matches(')').trim();
+            let text = text.trim();
+            Some(PrintedFelt {
+                value: value.to_string(),
+                text: (!text.is_empty()).then(|| text.to_string()),
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_parse_printed() {
+        let output =
+            "[DEBUG]\thello                          \t(raw: 448378203247                   )\n\
+            [DEBUG]\t                               \t(raw: 1                              ) \n\n";
+
+        assert_eq!(
+            parse_printed(output),
+            vec![
+                PrintedFelt {
+                    value: "448378203247".into(),
+                    text: Some("hello".into()),
+                },
+                PrintedFelt {
+                    value: "1".into(),
+                    text: None,
+                },
+            ]
+        );
+    }
+}
@@ -0,0 +1,205 @@
+//! Compiles Starknet contract exercises to a Sierra contract class and
+//! validates the resulting ABI against the one declared in `info.toml`.
+
+use std::fmt::{self, Display, Formatter};
+use std::path::Path;
+use std::sync::Arc;
+
+use anyhow::Context;
+use cairo_lang_compiler::db::RootDatabase;
+use cairo_lang_compiler::project::setup_project;
+use cairo_lang_plugins::get_default_plugins;
+use cairo_lang_starknet::contract_class::{compile_prepared_db, ContractClass};
+use cairo_lang_starknet::plugin::StarkNetPlugin;
+use console::style;
+use regex::Regex;
+use serde::Deserialize;
+use starklings::corelib::init_corelib;
+use starklings::diagnostics::check_diagnostics;
+
+/// Whether a contract function may modify the contract state.
+#[derive(Deserialize, Copy, Clone, Debug, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum StateMutability {
+    External,
+    View,
+}
+
+/// A function signature of a contract ABI.
+/// Declared by exercises in `info.toml` and read back from the compiled contract class.
+#[derive(Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct AbiFunction {
+    /// The name of the function.
+    pub name: String,
+    /// The types of the function parameters.
+    #[serde(default)]
+    pub inputs: Vec<String>,
+    /// The types of the function return values.
+    #[serde(default)]
+    pub outputs: Vec<String>,
+    /// Whether the function is an `#[external]` or a `#[view]`.
+    pub state_mutability: StateMutability,
+}
+
+impl Display for AbiFunction {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        let kind = match self.state_mutability {
+            StateMutability::External => "#[external]",
+            StateMutability::View => "#[view]",
+        };
+        write!(f, "{kind} fn {}({})", self.name, self.inputs.join(", "))?;
+        if !self.outputs.is_empty() {
+            write!(f, " -> ({})", self.outputs.join(", "))?;
+        }
+        Ok(())
+    }
+}
+
+/// The JSON representation of an ABI item, as serialized by `cairo-lang-starknet`.
+#[derive(Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum AbiItem {
+    Function {
+        name: String,
+        inputs: Vec<AbiInput>,
+        outputs: Vec<AbiOutput>,
+        state_mutability: StateMutability,
+    },
+    #[serde(other)]
+    Other,
+}
+
+#[derive(Deserialize)]
+struct AbiInput {
+    #[serde(rename = "type")]
+    ty: String,
+}
+
+#[derive(Deserialize)]
+struct AbiOutput {
+    #[serde(rename = "type")]
+    ty: String,
+}
+
+/// Compiles the contract at `path` into a Sierra contract class.
+pub fn compile_contract(path: &Path) -> anyhow::Result<ContractClass> {
+    let mut plugins = get_default_plugins();
+    plugins.push(Arc::new(StarkNetPlugin {}));
+    let mut db = RootDatabase::builder().with_plugins(plugins).build()?;
+    init_corelib(&mut db, None)?;
+
+    let main_crate_ids = setup_project(&mut db, path)?;
+
+    check_diagnostics(&db, &main_crate_ids, &path.display().to_string())?;
+
+    compile_prepared_db(db, main_crate_ids, true)
+        .with_context(|| format!("failed to compile contract class: {}", path.display()))
+}
+
+/// Extracts the function signatures from the ABI of a compiled contract class.
+pub fn abi_functions(contract_class: &ContractClass) -> anyhow::Result<Vec<AbiFunction>> {
+    let abi = contract_class
+        .abi
+        .as_ref()
+        .with_context(|| "The contract class has no ABI.")?;
+    let items: Vec<AbiItem> = serde_json::from_value(serde_json::to_value(abi)?)
+        .with_context(|| "Failed to read the contract ABI.")?;
+    Ok(items
+        .into_iter()
+        .filter_map(|item| match item {
+            AbiItem::Function {
+                name,
+                inputs,
+                outputs,
+                state_mutability,
+            } => Some(AbiFunction {
+                name,
+                inputs: inputs.into_iter().map(|input| input.ty).collect(),
+                outputs: outputs.into_iter().map(|output| output.ty).collect(),
+                state_mutability,
+            }),
+            AbiItem::Other => None,
+        })
+        .collect())
+}
+
+/// Compares the expected ABI against the actual one.
+/// Returns a readable diff if they don't match, `None` otherwise.
+pub fn abi_diff(expected: &[AbiFunction], actual: &[AbiFunction]) -> Option<String> {
+    let expected: Vec<AbiFunction> = expected.iter().map(normalize).collect();
+    let actual: Vec<AbiFunction> = actual.iter().map(normalize).collect();
+    let mut diff = vec![];
+    for function in &expected {
+        match actual.iter().find(|f| f.name == function.name) {
+            Some(found) if found == function => {}
+            Some(found) => {
+                diff.push(format!("{}", style(format!("- {function}")).red()));
+                diff.push(format!("{}", style(format!("+ {found}")).green()));
+            }
+            None => diff.push(format!("{}", style(format!("- {function}")).red())),
+        }
+    }
+    for function in &actual {
+        if !expected.iter().any(|f| f.name == function.name) {
+            diff.push(format!("{}", style(format!("+ {function}")).green()));
+        }
+    }
+    if diff.is_empty() {
+        None
+    } else {
+        Some(diff.join("\n"))
+    }
+}
+
+/// Strips the module paths from the types of a function, so `core::felt252`
+/// and `felt252` are considered the same type.
+fn normalize(function: &AbiFunction) -> AbiFunction {
+    let path_prefix = Regex::new(r"(\w+::)+").unwrap();
+    let strip = |ty: &String| path_prefix.replace_all(ty.trim(), "").into_owned();
+    AbiFunction {
+        name: function.name.clone(),
+        inputs: function.inputs.iter().map(strip).collect(),
+        outputs: function.outputs.iter().map(strip).collect(),
+        state_mutability: function.state_mutability,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn function(name: &str, inputs: &[&str], state_mutability: StateMutability) -> AbiFunction {
+        AbiFunction {
+            name: name.into(),
+            inputs: inputs.iter().map(|ty| ty.to_string()).collect(),
+            outputs: vec![],
+            state_mutability,
+        }
+    }
+
+    #[test]
+    fn test_abi_matches_ignoring_type_paths() {
+        let expected = [function(
+            "increase",
+            &["felt252"],
+            StateMutability::External,
+        )];
+        let actual = [function(
+            "increase",
+            &["core::felt252"],
+            StateMutability::External,
+        )];
+
+        assert_eq!(abi_diff(&expected, &actual), None);
+    }
+
+    #[test]
+    fn test_abi_mismatch_is_reported() {
+        let expected = [function("get_counter", &[], StateMutability::View)];
+        let actual = [function("get_counter", &[], StateMutability::External)];
+
+        let diff = abi_diff(&expected, &actual).unwrap();
+        assert!(diff.contains("- #[view] fn get_counter()"));
+        assert!(diff.contains("+ #[external] fn get_counter()"));
+    }
+}
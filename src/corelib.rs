@@ -0,0 +1,200 @@
+//! Locates the Cairo corelib the exercises are compiled against.
+
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::{bail, Context};
+use cairo_lang_compiler::db::RootDatabase;
+use cairo_lang_filesystem::db::init_dev_corelib;
+use serde::Deserialize;
+
+/// The environment variable holding the path to the corelib.
+pub const CORELIB_ENV: &str = "STARKLINGS_CORELIB";
+
+const CORELIB_DIR_NAME: &str = "corelib/src";
+const INFO_FILE_NAME: &str = "info.toml";
+
+/// The keys of `info.toml` the corelib lookup cares about.
+#[derive(Deserialize)]
+struct InfoFile {
+    corelib: Option<PathBuf>,
+}
+
+/// Where the corelib is read from.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Corelib {
+    /// The corelib `src` directory on disk.
+    Dir(PathBuf),
+    /// The copy embedded in the binary.
+    #[cfg(feature = "embedded-corelib")]
+    Embedded,
+}
+
+/// Adds the corelib to the database. It is looked up, in order, in:
+/// 1. the `explicit` path, given with `--corelib`;
+/// 2. the `STARKLINGS_CORELIB` environment variable;
+/// 3. the `corelib` key of the closest `info.toml`;
+/// 4. a `corelib/src` directory next to `info.toml` or in one of its parents;
+/// 5. the copy of the corelib embedded in the binary, when built with the
+///    `embedded-corelib` feature.
+pub fn init_corelib(db: &mut RootDatabase, explicit: Option<&Path>) -> anyhow::Result<()> {
+    match find_corelib(explicit)? {
+        Corelib::Dir(path) => init_dev_corelib(db, path),
+        #[cfg(feature = "embedded-corelib")]
+        Corelib::Embedded => embedded::init_embedded_corelib(db),
+    }
+    Ok(())
+}
+
+/// Finds the corelib in the order described in [`init_corelib`].
+pub fn find_corelib(explicit: Option<&Path>) -> anyhow::Result<Corelib> {
+    if let Some(path) = explicit {
+        return corelib_src(path)
+            .map(Corelib::Dir)
+            .with_context(|| format!("No corelib found at `{}`.", path.display()));
+    }
+    if let Some(path) = env::var_os(CORELIB_ENV) {
+        let path = PathBuf::from(path);
+        return corelib_src(&path).map(Corelib::Dir).with_context(|| {
+            format!(
+                "No corelib found at `{}`, as set by {CORELIB_ENV}.",
+                path.display()
+            )
+        });
+    }
+    let current_dir = env::current_dir().with_context(|| "Failed to get the current directory.")?;
+    if let Some(path) = find_in_ancestors(&current_dir)? {
+        return Ok(Corelib::Dir(path));
+    }
+    fallback_corelib()
+}
+
+/// The corelib used when none was found on disk.
+#[cfg(feature = "embedded-corelib")]
+fn fallback_corelib() -> anyhow::Result<Corelib> {
+    Ok(Corelib::Embedded)
+}
+
+#[cfg(not(feature = "embedded-corelib"))]
+fn fallback_corelib() -> anyhow::Result<Corelib> {
+    bail!(
+        "No corelib found. Pass its path with `--corelib` or {CORELIB_ENV}, \
+        or build with the `embedded-corelib` feature."
+    )
+}
+
+/// Looks for the corelib declared in, or sitting next to, an `info.toml`
+/// in `dir` or one of its parents.
+fn find_in_ancestors(dir: &Path) -> anyhow::Result<Option<PathBuf>> {
+    for ancestor in dir.ancestors() {
+        let info_path = ancestor.join(INFO_FILE_NAME);
+        if info_path.is_file() {
+            let content = fs::read_to_string(&info_path)
+                .with_context(|| format!("Failed to read `{}`.", info_path.display()))?;
+            let info: InfoFile = toml::from_str(&content)
+                .with_context(|| format!("Failed to parse `{}`.", info_path.display()))?;
+            if let Some(path) = info.corelib {
+                let path = ancestor.join(path);
+                return corelib_src(&path).map(Some).with_context(|| {
+                    format!(
+                        "No corelib found at `{}`, as set in `{}`.",
+                        path.display(),
+                        info_path.display()
+                    )
+                });
+            }
+        }
+        let path = ancestor.join(CORELIB_DIR_NAME);
+        if is_corelib_src(&path) {
+            return Ok(Some(path));
+        }
+    }
+    Ok(None)
+}
+
+/// Accepts either the corelib `src` directory or the corelib package containing it.
+fn corelib_src(path: &Path) -> anyhow::Result<PathBuf> {
+    if is_corelib_src(path) {
+        return Ok(path.to_path_buf());
+    }
+    let src = path.join("src");
+    if is_corelib_src(&src) {
+        return Ok(src);
+    }
+    bail!("`{}` has no `lib.cairo`.", path.display())
+}
+
+fn is_corelib_src(path: &Path) -> bool {
+    path.join("lib.cairo").is_file()
+}
+
+#[cfg(feature = "embedded-corelib")]
+mod embedded {
+    use std::path::Path;
+    use std::sync::Arc;
+
+    use cairo_lang_compiler::db::RootDatabase;
+    use cairo_lang_filesystem::db::{init_dev_corelib, FilesGroupEx};
+    use cairo_lang_filesystem::ids::FileId;
+    use include_dir::{include_dir, Dir, DirEntry};
+
+    /// The corelib the binary was built with.
+    static EMBEDDED_CORELIB: Dir = include_dir!("$CARGO_MANIFEST_DIR/corelib/src");
+
+    /// The directory the embedded corelib pretends to live in.
+    /// Nothing is read from it, every file is served from memory.
+    pub const EMBEDDED_CORELIB_ROOT: &str = "/starklings-embedded-corelib/src";
+
+    /// Adds the embedded corelib to the database, overriding the content of
+    /// every one of its files so they are never looked up on disk.
+    pub fn init_embedded_corelib(db: &mut RootDatabase) {
+        let root = Path::new(EMBEDDED_CORELIB_ROOT);
+        init_dev_corelib(db, root.to_path_buf());
+        override_files(db, root, &EMBEDDED_CORELIB);
+    }
+
+    fn override_files(db: &mut RootDatabase, root: &Path, dir: &Dir) {
+        for entry in dir.entries() {
+            match entry {
+                DirEntry::Dir(dir) => override_files(db, root, dir),
+                DirEntry::File(file) => {
+                    let Some(content) = file.contents_utf8() else {
+                        continue;
+                    };
+                    let file_id = FileId::new(db, root.join(file.path()));
+                    db.override_file_content(file_id, Some(Arc::new(content.to_string())));
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_corelib_package_resolves_to_src() {
+        let package = Path::new(env!("CARGO_MANIFEST_DIR")).join("corelib");
+
+        assert_eq!(corelib_src(&package).unwrap(), package.join("src"));
+        assert!(corelib_src(&package.join("src")).is_ok());
+        assert!(corelib_src(Path::new(env!("CARGO_MANIFEST_DIR"))).is_err());
+    }
+
+    #[cfg(feature = "embedded-corelib")]
+    #[test]
+    fn test_embedded_corelib_is_served_from_memory() {
+        use cairo_lang_filesystem::db::FilesGroup;
+        use cairo_lang_filesystem::ids::FileId;
+
+        let mut db = RootDatabase::default();
+        embedded::init_embedded_corelib(&mut db);
+
+        let root = Path::new(embedded::EMBEDDED_CORELIB_ROOT);
+        let lib = FileId::new(&db, root.join("lib.cairo"));
+        assert!(!root.exists());
+        assert!(db.file_content(lib).is_some());
+    }
+}
@@ -0,0 +1,59 @@
+//! Compiler databases kept alive between compilations.
+//! Salsa memoizes the parsing and semantic analysis of the corelib, so
+//! compiling an exercise again in the same database only redoes the work
+//! for the exercise file that changed.
+
+use std::cell::RefCell;
+use std::collections::hash_map::Entry;
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+use std::sync::Arc;
+
+use cairo_lang_compiler::db::RootDatabase;
+use cairo_lang_compiler::project::setup_project;
+use cairo_lang_filesystem::db::FilesGroupEx;
+use cairo_lang_filesystem::ids::{CrateId, FileId};
+
+/// The kinds of databases, which differ in the compiler plugins they load.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum DatabaseKind {
+    /// The database used to compile and run `main`.
+    Runner,
+    /// The database used to compile and run tests, with or without the Starknet plugin.
+    Tester { starknet: bool },
+}
+
+thread_local! {
+    static DATABASES: RefCell<HashMap<DatabaseKind, RootDatabase>> = RefCell::new(HashMap::new());
+}
+
+/// Calls `f` with the database of the given kind, building it with `build`
+/// the first time it is needed on the current thread. Later calls on the same
+/// thread reuse it, so compiling and running exercises only pays for the
+/// corelib once per thread.
+pub fn with_database<T>(
+    kind: DatabaseKind,
+    build: impl FnOnce() -> anyhow::Result<RootDatabase>,
+    f: impl FnOnce(&mut RootDatabase) -> anyhow::Result<T>,
+) -> anyhow::Result<T> {
+    DATABASES.with(|databases| {
+        let mut databases = databases.borrow_mut();
+        let db = match databases.entry(kind) {
+            Entry::Occupied(entry) => entry.into_mut(),
+            Entry::Vacant(entry) => entry.insert(build()?),
+        };
+        f(db)
+    })
+}
+
+/// Sets up the crate of an exercise in the database.
+/// The compiler caches the files it reads from disk, so the content of the
+/// exercise is read again and overridden in the database on every call.
+pub fn setup_exercise(db: &mut RootDatabase, path: &Path) -> anyhow::Result<Vec<CrateId>> {
+    let main_crate_ids = setup_project(db, path)?;
+    let content = fs::read_to_string(path)?;
+    let file_id = FileId::new(db, path.to_path_buf());
+    db.override_file_content(file_id, Some(Arc::new(content)));
+    Ok(main_crate_ids)
+}
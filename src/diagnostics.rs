@@ -0,0 +1,297 @@
+//! Structured compiler diagnostics.
+//! The diagnostics are collected from the compiler database, instead of being
+//! printed to stderr, so they can be rendered with source snippets or reported as JSON.
+
+use std::fmt::{self, Display, Formatter};
+use std::fs;
+
+use cairo_lang_compiler::db::RootDatabase;
+use cairo_lang_defs::db::DefsGroup;
+use cairo_lang_defs::ids::ModuleId;
+use cairo_lang_diagnostics::{DiagnosticEntry, DiagnosticLocation, Diagnostics};
+use cairo_lang_filesystem::db::FilesGroup;
+use cairo_lang_filesystem::ids::CrateId;
+use cairo_lang_filesystem::span::TextOffset;
+use cairo_lang_lowering::db::LoweringGroup;
+use cairo_lang_parser::db::ParserGroup;
+use cairo_lang_semantic::db::SemanticGroup;
+use cairo_lang_utils::Upcast;
+use console::style;
+use serde::Serialize;
+
+/// How serious a diagnostic is.
+/// The compiler only reports errors for now.
+#[derive(Serialize, Copy, Clone, Debug, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+    Error,
+}
+
+/// A line and column in a file, both starting at 1.
+#[derive(Serialize, Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Position {
+    pub line: usize,
+    pub column: usize,
+}
+
+/// A point in a source file, shown as `file:line:column`,
+/// or as the file alone when the position is unknown.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SourceLocation {
+    pub file: String,
+    pub position: Option<Position>,
+}
+
+impl SourceLocation {
+    /// Where a location of the compiler starts.
+    pub fn start_of(db: &dyn FilesGroup, location: &DiagnosticLocation) -> SourceLocation {
+        SourceLocation {
+            file: location.file_id.file_name(db),
+            position: position_in_file(db, location, location.span.start),
+        }
+    }
+}
+
+impl Display for SourceLocation {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self.position {
+            Some(position) => write!(f, "{}:{}:{}", self.file, position.line, position.column),
+            None => write!(f, "{}", self.file),
+        }
+    }
+}
+
+/// A single compiler diagnostic.
+#[derive(Serialize, Clone, Debug, PartialEq, Eq)]
+pub struct Diagnostic {
+    /// The file the diagnostic points to.
+    pub file: String,
+    /// Where the diagnostic starts in the file.
+    pub start: Option<Position>,
+    /// Where the diagnostic ends in the file.
+    pub end: Option<Position>,
+    pub severity: Severity,
+    pub message: String,
+}
+
+impl Diagnostic {
+    /// Where the diagnostic starts.
+    pub fn location(&self) -> SourceLocation {
+        SourceLocation {
+            file: self.file.clone(),
+            position: self.start,
+        }
+    }
+
+    /// Renders the diagnostic followed by the source lines it points to.
+    pub fn render(&self) -> String {
+        let mut rendered = format!("{}: {}", style("error").red().bold(), self.message);
+        let Some(start) = self.start else {
+            rendered.push_str(&format!("\n --> {}", self.file));
+            return rendered;
+        };
+        rendered.push_str(&format!(
+            "\n --> {}:{}:{}",
+            self.file, start.line, start.column
+        ));
+        let Ok(source) = fs::read_to_string(&self.file) else {
+            return rendered;
+        };
+        let end = self.end.unwrap_or(start);
+        let gutter = end.line.to_string().len();
+        for (index, line) in source
+            .lines()
+            .enumerate()
+            .skip(start.line - 1)
+            .take(end.line + 1 - start.line)
+        {
+            let number = index + 1;
+            rendered.push_str(&format!(
+                "\n{} {}  {line}",
+                style(format!("{number:>gutter$}")).blue().bold(),
+                style("|").blue()
+            ));
+            let from = if number == start.line {
+                start.column
+            } else {
+                1
+            };
+            let to = if number == end.line {
+                end.column
+            } else {
+                line.chars().count() + 1
+            };
+            if to > from {
+                rendered.push_str(&format!(
+                    "\n{:gutter$} {}  {}{}",
+                    "",
+                    style("|").blue(),
+                    " ".repeat(from - 1),
+                    style("^".repeat(to - from)).red().bold()
+                ));
+            }
+        }
+        rendered
+    }
+}
+
+impl Display for Diagnostic {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "{}: {}", self.location(), self.message)
+    }
+}
+
+/// The error returned when a program does not compile.
+#[derive(Debug)]
+pub struct CompilationFailed {
+    /// The path of the program that failed to compile.
+    pub path: String,
+    pub diagnostics: Vec<Diagnostic>,
+}
+
+impl CompilationFailed {
+    /// Renders every diagnostic with the source lines it points to.
+    pub fn render(&self) -> String {
+        let mut rendered = String::new();
+        for diagnostic in &self.diagnostics {
+            rendered.push_str(&diagnostic.render());
+            rendered.push_str("\n\n");
+        }
+        rendered.push_str(&format!("failed to compile: {}", self.path));
+        rendered
+    }
+}
+
+impl Display for CompilationFailed {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        for diagnostic in &self.diagnostics {
+            writeln!(f, "{diagnostic}")?;
+        }
+        write!(f, "failed to compile: {}", self.path)
+    }
+}
+
+impl std::error::Error for CompilationFailed {}
+
+/// Fails with the diagnostics of the given crates, if there are any.
+pub fn check_diagnostics(
+    db: &RootDatabase,
+    crate_ids: &[CrateId],
+    path: &str,
+) -> Result<(), CompilationFailed> {
+    let diagnostics = collect_diagnostics(db, crate_ids);
+    if diagnostics.is_empty() {
+        Ok(())
+    } else {
+        Err(CompilationFailed {
+            path: path.to_string(),
+            diagnostics,
+        })
+    }
+}
+
+/// Collects the syntax, semantic and lowering diagnostics of the given crates.
+/// A database may hold the crates of other exercises, whose diagnostics are not reported.
+pub fn collect_diagnostics(db: &RootDatabase, crate_ids: &[CrateId]) -> Vec<Diagnostic> {
+    let mut diagnostics = vec![];
+    for crate_id in crate_ids.iter().copied() {
+        let Ok(main_file) = db.module_main_file(ModuleId::CrateRoot(crate_id)) else {
+            diagnostics.push(file_diagnostic(
+                String::new(),
+                "Failed to get main module file",
+            ));
+            continue;
+        };
+        if db.file_content(main_file).is_none() {
+            diagnostics.push(file_diagnostic(main_file.file_name(db), "File not found"));
+        }
+        for module_id in db.crate_modules(crate_id).iter() {
+            for file_id in db.module_files(*module_id).unwrap_or_default().iter() {
+                push_diagnostics(db.file_syntax_diagnostics(*file_id), db, &mut diagnostics);
+            }
+            if let Ok(module_diagnostics) = db.module_semantic_diagnostics(*module_id) {
+                push_diagnostics(module_diagnostics, db, &mut diagnostics);
+            }
+            if let Ok(module_diagnostics) = db.module_lowering_diagnostics(*module_id) {
+                push_diagnostics(module_diagnostics, db, &mut diagnostics);
+            }
+        }
+    }
+    diagnostics
+}
+
+/// A diagnostic about a whole file, without a span.
+fn file_diagnostic(file: String, message: &str) -> Diagnostic {
+    Diagnostic {
+        file,
+        start: None,
+        end: None,
+        severity: Severity::Error,
+        message: message.to_string(),
+    }
+}
+
+/// Converts the entries of a compiler diagnostics group and appends them to `diagnostics`.
+fn push_diagnostics<TEntry: DiagnosticEntry>(
+    entries: Diagnostics<TEntry>,
+    db: &TEntry::DbType,
+    diagnostics: &mut Vec<Diagnostic>,
+) {
+    for entry in entries.get_all() {
+        diagnostics.push(diagnostic_at(
+            db.upcast(),
+            &entry.location(db),
+            entry.format(db),
+        ));
+    }
+}
+
+/// Builds an error diagnostic pointing to a location in a file.
+pub fn diagnostic_at(
+    db: &dyn FilesGroup,
+    location: &DiagnosticLocation,
+    message: String,
+) -> Diagnostic {
+    Diagnostic {
+        file: location.file_id.file_name(db),
+        start: position_in_file(db, location, location.span.start),
+        end: position_in_file(db, location, location.span.end),
+        severity: Severity::Error,
+        message,
+    }
+}
+
+/// The position of an offset in the file of a location of the compiler.
+fn position_in_file(
+    db: &dyn FilesGroup,
+    location: &DiagnosticLocation,
+    offset: TextOffset,
+) -> Option<Position> {
+    offset
+        .position_in_file(db, location.file_id)
+        .map(|position| Position {
+            line: position.line + 1,
+            column: position.col + 1,
+        })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_diagnostic_display() {
+        let diagnostic = Diagnostic {
+            file: "exercises/intro/intro2.cairo".into(),
+            start: Some(Position { line: 2, column: 5 }),
+            end: Some(Position { line: 2, column: 7 }),
+            severity: Severity::Error,
+            message: "Function not found.".into(),
+        };
+
+        assert_eq!(
+            diagnostic.to_string(),
+            "exercises/intro/intro2.cairo:2:5: Function not found."
+        );
+    }
+}
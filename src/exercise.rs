@@ -1,16 +1,28 @@
+use cairo_lang_runner::RunResultValue;
 use regex::Regex;
-use serde::Deserialize;
+use serde::de::{self, Deserializer};
+use serde::{Deserialize, Serialize};
+use starklings::capture::PrintedFelt;
+use starklings::diagnostics::CompilationFailed;
+use starklings::gas::gas_used;
 
-use crate::starklings_runner::{run_cairo_program, Args as RunnerArgs};
-use crate::starklings_tester::{test_cairo_program, Args as TesterArgs};
+use crate::contract::{abi_diff, abi_functions, compile_contract, AbiFunction};
+use crate::output::{describe_gas, describe_run_result, ExpectedOutput};
+use crate::progress::{source_hash, Progress};
+use crate::starklings_runner::{
+    describe_printed, execute_cairo_cases, execute_cairo_program, Args as RunnerArgs,
+    ProgramPanicked, ProgramRun,
+};
+use crate::starklings_tester::{run_cairo_tests, Args as TesterArgs, TestsSummary};
 use std::fmt::{self, Display, Formatter};
-use std::fs::{remove_file, File};
-use std::io::Read;
+use std::fs::remove_file;
+use std::io;
 use std::path::PathBuf;
 use std::process::{self};
+use std::slice;
 
-const I_AM_DONE_REGEX: &str = r"(?m)^\s*///?\s*I\s+AM\s+NOT\s+DONE";
-const CONTEXT: usize = 2;
+// The gas `main` is run with
+const AVAILABLE_GAS: usize = 20000000000;
 
 // Get a temporary file name that is hopefully unique
 #[inline]
@@ -24,13 +36,17 @@ fn temp_file() -> String {
 }
 
 // The mode of the exercise.
-#[derive(Deserialize, Copy, Clone, Debug)]
+#[derive(Deserialize, Serialize, Copy, Clone, Debug)]
 #[serde(rename_all = "lowercase")]
 pub enum Mode {
     // Indicates that the exercise should be compiled as a binary
     Compile,
     // Indicates that the exercise should be tested
     Test,
+    // Indicates that the exercise is a Starknet contract whose tests should be run
+    Starknet,
+    // Indicates that the exercise should be run and its output compared against the expected one
+    Output,
 }
 
 #[derive(Deserialize)]
@@ -40,37 +56,108 @@ pub struct ExerciseList {
 
 // A representation of a starklings exercise.
 // This is deserialized from the accompanying info.toml file
-#[derive(Deserialize, Debug)]
+#[derive(Deserialize, Clone, Debug)]
 pub struct Exercise {
     // Name of the exercise
     pub name: String,
     // The path to the file containing the exercise's source code
     pub path: PathBuf,
-    // The mode of the exercise (Test, Compile, or Clippy)
+    // The mode of the exercise (Test, Compile, Starknet or Output)
     pub mode: Mode,
-    // The hint text associated with the exercise
+    // The hint associated with the exercise, revealed one level at a time
+    pub hint: Hint,
+    // The external and view functions a Starknet exercise must expose
+    #[serde(default)]
+    pub abi: Vec<AbiFunction>,
+    // The result `main` must produce in an Output exercise
+    pub expected_output: Option<ExpectedOutput>,
+    // Whether `main` is expected to panic in a Compile exercise
+    #[serde(default)]
+    pub expect_panic: bool,
+    // The most gas the solution may consume, for optimization exercises
+    pub max_gas: Option<usize>,
+    // The felt arguments `main` is run with
+    #[serde(default)]
+    pub args: Vec<String>,
+    // The arguments and expected outputs `main` is checked against in a Compile exercise
+    #[serde(default)]
+    pub cases: Vec<Case>,
+    // Hints targeted at common mistakes, shown when verify fails with a matching error
+    #[serde(default)]
+    pub diagnostic_hints: Vec<DiagnosticHint>,
+}
+
+// The hint of an exercise, from the gentlest nudge to the full answer.
+// info.toml holds either a single hint or an ordered list of levels
+#[derive(Deserialize, Clone, Debug)]
+#[serde(untagged)]
+pub enum Hint {
+    Single(String),
+    Levels(Vec<String>),
+}
+
+impl Hint {
+    // The levels of the hint, in the order they are revealed
+    pub fn levels(&self) -> &[String] {
+        match self {
+            Hint::Single(hint) => slice::from_ref(hint),
+            Hint::Levels(levels) => levels,
+        }
+    }
+
+    // Describes a level of the hint, counting from 1.
+    // A single hint is shown as is, without numbering it
+    pub fn describe_level(&self, level: usize) -> String {
+        let levels = self.levels();
+        let Some(hint) = level.checked_sub(1).and_then(|i| levels.get(i)) else {
+            return String::new();
+        };
+        if levels.len() == 1 {
+            hint.clone()
+        } else {
+            format!("Pista {level}/{}:\n{hint}", levels.len())
+        }
+    }
+}
+
+// A hint targeted at a mistake, recognized by the error it causes
+#[derive(Deserialize, Clone, Debug)]
+pub struct DiagnosticHint {
+    // The regex matched against the compiler diagnostics,
+    // or against the whole error when the exercise compiled
+    #[serde(deserialize_with = "deserialize_regex")]
+    pub pattern: Regex,
+    // The hint shown when the pattern matches
     pub hint: String,
 }
 
+fn deserialize_regex<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Regex, D::Error> {
+    let pattern = String::deserialize(deserializer)?;
+    Regex::new(&pattern).map_err(de::Error::custom)
+}
+
+// A set of arguments of `main` and the output it must produce with them
+#[derive(Deserialize, Clone, Debug)]
+pub struct Case {
+    // The felt arguments `main` is run with
+    #[serde(default)]
+    pub args: Vec<String>,
+    // The result `main` must produce, returning successfully by default
+    #[serde(default)]
+    pub expected_output: ExpectedOutput,
+}
+
 // An enum to track of the state of an Exercise.
-// An Exercise can be either Done or Pending
-#[derive(PartialEq, Debug)]
+// An Exercise can be either Done, Modified or Pending
+#[derive(Serialize, PartialEq, Debug)]
+#[serde(rename_all = "lowercase")]
 pub enum State {
-    // The state of the exercise once it's been completed
+    // The state of the exercise once its current source has passed verify
     Done,
+    // The state of the exercise when it was solved but has been edited since
+    Modified,
     // The state of the exercise while it's not completed yet
-    Pending(Vec<ContextLine>),
-}
-
-// The context information of a pending exercise
-#[derive(PartialEq, Debug)]
-pub struct ContextLine {
-    // The source code that is still pending completion
-    pub line: String,
-    // The line number of the source code still pending completion
-    pub number: usize,
-    // Whether or not this is important
-    pub important: bool,
+    Pending,
 }
 
 // A representation of an already executed binary
@@ -82,6 +169,58 @@ pub struct ExerciseOutput {
     pub stderr: String,
 }
 
+// The result of running `main`, with the gas it consumed and the felts it printed
+#[derive(Debug)]
+pub struct ExerciseRun {
+    pub value: RunResultValue,
+    pub gas_used: Option<usize>,
+    pub printed: Vec<PrintedFelt>,
+}
+
+// The error returned when a solution consumes more gas than the exercise allows
+#[derive(Debug)]
+pub struct GasBudgetExceeded {
+    pub gas_used: usize,
+    pub max_gas: usize,
+}
+
+impl Display for GasBudgetExceeded {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(
+            f,
+            "La solución consume {} de gas, pero el máximo permitido es {}",
+            self.gas_used, self.max_gas
+        )
+    }
+}
+
+impl std::error::Error for GasBudgetExceeded {}
+
+// The error returned when some of the cases of an exercise failed
+#[derive(Debug)]
+pub struct CasesFailed {
+    // The number of cases of the exercise
+    pub total: usize,
+    // The failed cases, numbered from 1, with their arguments and why they failed
+    pub failed: Vec<(usize, Vec<String>, String)>,
+}
+
+impl Display for CasesFailed {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "Fallan {} de {} casos:", self.failed.len(), self.total)?;
+        for (number, args, error) in &self.failed {
+            write!(
+                f,
+                "\n\ncaso {number} (argumentos [{}]):\n{error}",
+                args.join(", ")
+            )?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for CasesFailed {}
+
 struct FileHandle;
 
 impl Drop for FileHandle {
@@ -91,73 +230,233 @@ impl Drop for FileHandle {
 }
 
 impl Exercise {
+    // Runs `main` and applies the rules of the exercise mode to its result:
+    // Output exercises must produce the expected output, and Compile exercises
+    // must not panic unless they declare `expect_panic`.
+    pub fn run_result(&self) -> anyhow::Result<ExerciseRun> {
+        let ProgramRun { result, printed } = execute_cairo_program(&self.runner_args())?;
+        let gas_used = gas_used(Some(AVAILABLE_GAS), &result);
+        let value = if let Mode::Output = self.mode {
+            let expected_output = self.expected_output.as_ref().ok_or_else(|| {
+                anyhow::anyhow!(
+                    "El ejercicio {} no declara `expected_output` en info.toml",
+                    self.name
+                )
+            })?;
+            expected_output.check(&result.value, &printed)?;
+            result.value
+        } else {
+            match (result.value, self.expect_panic) {
+                (RunResultValue::Panic(values), false) => {
+                    return Err(ProgramPanicked { values, printed }.into())
+                }
+                (RunResultValue::Success(_), true) => {
+                    anyhow::bail!("Se esperaba que main hiciera panic, pero terminó con éxito")
+                }
+                (value, _) => value,
+            }
+        };
+        self.check_gas(gas_used)?;
+        Ok(ExerciseRun {
+            value,
+            gas_used,
+            printed,
+        })
+    }
+
     pub fn run_cairo(&self) -> anyhow::Result<String> {
-        run_cairo_program(&RunnerArgs {
-            path: self.path.to_str().unwrap().parse()?,
-            available_gas: Some(20000000000),
+        if !self.cases.is_empty() {
+            return self.run_cases();
+        }
+        let run = self.run_result()?;
+        let mut output = String::new();
+        if !run.printed.is_empty() {
+            output.push_str(&format!("{}\n", describe_printed(&run.printed)));
+        }
+        output.push_str(&describe_run_result(&run.value));
+        if let Some(gas_used) = run.gas_used {
+            output.push_str(&format!("\n{}", describe_gas(gas_used, self.max_gas)));
+        }
+        Ok(output)
+    }
+
+    // Runs `main` with the arguments of every case, compiling it only once,
+    // and checks each run against the expected output of its case
+    pub fn run_cases(&self) -> anyhow::Result<String> {
+        let cases_args = self
+            .cases
+            .iter()
+            .map(|case| case.args.clone())
+            .collect::<Vec<_>>();
+        let runs = execute_cairo_cases(&self.runner_args(), &cases_args)?;
+        let mut failed = vec![];
+        for (number, (case, run)) in self.cases.iter().zip(runs).enumerate() {
+            let checked = run.and_then(|run| {
+                case.expected_output
+                    .check(&run.result.value, &run.printed)?;
+                self.check_gas(gas_used(Some(AVAILABLE_GAS), &run.result))?;
+                Ok(())
+            });
+            if let Err(error) = checked {
+                failed.push((number + 1, case.args.clone(), error.to_string()));
+            }
+        }
+        if !failed.is_empty() {
+            return Err(CasesFailed {
+                total: self.cases.len(),
+                failed,
+            }
+            .into());
+        }
+        Ok(format!(
+            "Los {} casos devuelven el resultado esperado",
+            self.cases.len()
+        ))
+    }
+
+    // The arguments `main` is compiled and run with
+    fn runner_args(&self) -> RunnerArgs {
+        RunnerArgs {
+            path: self.path.to_str().unwrap().to_string(),
+            available_gas: Some(AVAILABLE_GAS),
             print_full_memory: false,
-        })
+            corelib: None,
+            args: self.args.clone(),
+        }
     }
 
-    pub fn test_cairo(&self) -> anyhow::Result<String> {
-        test_cairo_program(&TesterArgs {
+    pub fn test_results(&self) -> anyhow::Result<TestsSummary> {
+        run_cairo_tests(&TesterArgs {
             path: self.path.to_str().unwrap().parse()?,
             filter: "".to_string(),
             include_ignored: false,
             ignored: false,
-            starknet: false,
+            starknet: matches!(self.mode, Mode::Starknet),
+            corelib: None,
+            sort_by: None,
+            snapshot: false,
+            check_snapshot: false,
+            snapshot_file: PathBuf::from(".gas-snapshot"),
+            tolerance: 0.0,
+            jobs: None,
         })
     }
 
-    pub fn state(&self) -> State {
-        let mut source_file = File::open(&self.path).unwrap_or_else(|_| panic!("We were unable to open the exercise file! {:?}",
-            self.path));
-
-        let source = {
-            let mut s = String::new();
-            source_file
-                .read_to_string(&mut s)
-                .expect("We were unable to read the exercise file!");
-            s
-        };
+    pub fn test_cairo(&self) -> anyhow::Result<String> {
+        self.check_tests(self.test_results()?)
+    }
 
-        let re = Regex::new(I_AM_DONE_REGEX).unwrap();
+    // Applies the rules of the exercise mode to the results of its tests:
+    // they must all pass within the gas budget, and Starknet exercises must
+    // also compile to a contract class with the ABI declared in info.toml
+    pub fn check_tests(&self, summary: TestsSummary) -> anyhow::Result<String> {
+        let gas_used = summary.gas_used;
+        let output = summary.into_result()?;
+        self.check_gas(Some(gas_used))?;
+        if let Mode::Starknet = self.mode {
+            self.check_abi()?;
+        }
+        Ok(output)
+    }
 
-        if !re.is_match(&source) {
-            return State::Done;
+    // Fails if the exercise declares a gas budget and the solution exceeds it.
+    // Tests are budgeted by the gas consumed by all of them together
+    pub fn check_gas(&self, gas_used: Option<usize>) -> Result<(), GasBudgetExceeded> {
+        match (gas_used, self.max_gas) {
+            (Some(gas_used), Some(max_gas)) if gas_used > max_gas => {
+                Err(GasBudgetExceeded { gas_used, max_gas })
+            }
+            _ => Ok(()),
         }
+    }
 
-        let matched_line_index = source
-            .lines()
-            .enumerate()
-            .find_map(|(i, line)| if re.is_match(line) { Some(i) } else { None })
-            .expect("This should not happen at all");
+    // Compiles a Starknet exercise into a contract class and, if info.toml
+    // declares the expected ABI, compares it against the compiled one.
+    pub fn check_abi(&self) -> anyhow::Result<()> {
+        let contract_class = compile_contract(&self.path)?;
+        if self.abi.is_empty() {
+            return Ok(());
+        }
+        if let Some(diff) = abi_diff(&self.abi, &abi_functions(&contract_class)?) {
+            anyhow::bail!("El ABI del contrato no coincide con el esperado:\n{diff}");
+        }
+        Ok(())
+    }
 
-        let min_line = ((matched_line_index as i32) - (CONTEXT as i32)).max(0) as usize;
-        let max_line = matched_line_index + CONTEXT;
+    pub fn state(&self) -> State {
+        self.state_in(&Progress::load())
+    }
 
-        let context = source
-            .lines()
-            .enumerate()
-            .filter(|&(i, _)| i >= min_line && i <= max_line)
-            .map(|(i, line)| ContextLine {
-                line: line.to_string(),
-                number: i + 1,
-                important: i == matched_line_index,
-            })
-            .collect();
+    // Computes the state of the exercise from the recorded progress,
+    // comparing the source that last passed verify with the one on disk
+    pub fn state_in(&self, progress: &Progress) -> State {
+        let Some(verified_hash) = progress.verified_hash(&self.path) else {
+            return State::Pending;
+        };
+        match source_hash(&self.path) {
+            Ok(hash) if hash == verified_hash => State::Done,
+            _ => State::Modified,
+        }
+    }
 
-        State::Pending(context)
+    // Records the current source of the exercise as verified
+    pub fn mark_verified(&self) -> io::Result<()> {
+        let mut progress = Progress::load();
+        progress.mark_verified(&self.path, source_hash(&self.path)?);
+        progress.save()
     }
 
-    // Check that the exercise looks to be solved using self.state()
-    // This is not the best way to check since
-    // the user can just remove the "I AM NOT DONE" string from the file
-    // without actually having solved anything.
-    // The only other way to truly check this would to compile and run
-    // the exercise; which would be both costly and counterintuitive
-    pub fn looks_done(&self) -> bool {
-        self.state() == State::Done
+    // The number of hint levels revealed so far
+    pub fn hint_level_in(&self, progress: &Progress) -> usize {
+        progress
+            .hint_level(&self.path)
+            .min(self.hint.levels().len())
+    }
+
+    // Reveals the next level of the hint, recording it in the progress.
+    // Returns the number of levels revealed, which stays at the last level
+    // once all of them were revealed, along with the result of saving it
+    pub fn reveal_hint(&self) -> (usize, io::Result<()>) {
+        let mut progress = Progress::load();
+        let level = (self.hint_level_in(&progress) + 1).min(self.hint.levels().len());
+        if level == progress.hint_level(&self.path) {
+            return (level, Ok(()));
+        }
+        progress.set_hint_level(&self.path, level);
+        (level, progress.save())
+    }
+
+    // The diagnostic hints matching the error the exercise failed with.
+    // When it did not compile, each compiler diagnostic is matched on its own
+    pub fn diagnostic_hints_for(&self, error: &anyhow::Error) -> Vec<&str> {
+        let messages = match error.downcast_ref::<CompilationFailed>() {
+            Some(failed) => failed
+                .diagnostics
+                .iter()
+                .map(|diagnostic| diagnostic.message.clone())
+                .collect(),
+            None => vec![error.to_string()],
+        };
+        self.diagnostic_hints
+            .iter()
+            .filter(|hint| {
+                messages
+                    .iter()
+                    .any(|message| hint.pattern.is_match(message))
+            })
+            .map(|hint| hint.hint.as_str())
+            .collect()
+    }
+
+    // Forgets the verified source of the exercise after it failed verify.
+    // Returns whether the exercise had been solved before
+    pub fn clear_verified(&self) -> io::Result<bool> {
+        let mut progress = Progress::load();
+        let was_verified = progress.clear_verified(&self.path);
+        if was_verified {
+            progress.save()?;
+        }
+        Ok(was_verified)
     }
 }
 
@@ -175,29 +474,140 @@ fn clean() {
 #[cfg(test)]
 mod test {
     use super::*;
-    // use std::path::Path;
+    use starklings::diagnostics::{Diagnostic, Severity};
 
-    #[test]
-    fn test_finished_exercise() {
-        let exercise = Exercise {
-            name: "finished_exercise".into(),
-            path: PathBuf::from("tests/fixture/cairo/compilePass.cairo"),
+    fn exercise(name: &str, path: &str) -> Exercise {
+        Exercise {
+            name: name.into(),
+            path: PathBuf::from(path),
             mode: Mode::Compile,
-            hint: String::new(),
-        };
+            hint: Hint::Single(String::new()),
+            abi: vec![],
+            expected_output: None,
+            expect_panic: false,
+            max_gas: None,
+            args: vec![],
+            cases: vec![],
+            diagnostic_hints: vec![],
+        }
+    }
 
-        assert_eq!(exercise.state(), State::Done);
+    #[test]
+    fn test_unverified_exercise_is_pending() {
+        let exercise = exercise("finished_exercise", "tests/fixture/cairo/compilePass.cairo");
+
+        assert_eq!(exercise.state_in(&Progress::default()), State::Pending);
     }
 
     #[test]
-    fn test_cairo_test_passes() {
-        let exercise = Exercise {
-            name: "testPass".into(),
-            path: PathBuf::from("tests/fixture/cairo/testPass.cairo"),
-            mode: Mode::Compile,
-            hint: String::new(),
-        };
+    fn test_verified_exercise_is_done() {
+        let exercise = exercise("testPass", "tests/fixture/cairo/testPass.cairo");
+        let mut progress = Progress::default();
+        progress.mark_verified(&exercise.path, source_hash(&exercise.path).unwrap());
+
+        assert_eq!(exercise.state_in(&progress), State::Done);
+    }
+
+    #[test]
+    fn test_solution_progress_is_kept_apart() {
+        let exercise = exercise("testPass", "tests/fixture/cairo/testPass.cairo");
+        let mut solution = exercise.clone();
+        solution.path = PathBuf::from("tests/fixture/cairo/testFails.cairo");
+        let mut progress = Progress::default();
+        progress.mark_verified(&solution.path, source_hash(&solution.path).unwrap());
+
+        assert_eq!(solution.state_in(&progress), State::Done);
+        assert_eq!(exercise.state_in(&progress), State::Pending);
+    }
+
+    #[test]
+    fn test_edited_exercise_is_modified() {
+        let exercise = exercise("testPass", "tests/fixture/cairo/testPass.cairo");
+        let mut progress = Progress::default();
+        progress.mark_verified(&exercise.path, "outdated".to_string());
+
+        assert_eq!(exercise.state_in(&progress), State::Modified);
+    }
+
+    #[test]
+    fn test_gas_budget() {
+        let mut exercise = exercise("cairoPass", "tests/fixture/cairo/compilePass.cairo");
+        assert!(exercise.check_gas(Some(100)).is_ok());
+
+        exercise.max_gas = Some(50);
+        assert!(exercise.check_gas(Some(50)).is_ok());
+        assert!(exercise.check_gas(None).is_ok());
+        assert!(exercise.check_gas(Some(51)).is_err());
+    }
+
+    #[test]
+    fn test_hint_levels() {
+        let list: ExerciseList = toml::from_str(
+            r#"
+            [[exercises]]
+            name = "single"
+            path = "single.cairo"
+            mode = "compile"
+            hint = "Just one"
+
+            [[exercises]]
+            name = "levels"
+            path = "levels.cairo"
+            mode = "compile"
+            hint = ["A nudge", "The answer"]
+            "#,
+        )
+        .unwrap();
+        let (single, levels) = (&list.exercises[0].hint, &list.exercises[1].hint);
+
+        assert_eq!(single.describe_level(1), "Just one");
+        assert_eq!(levels.levels().len(), 2);
+        assert_eq!(levels.describe_level(1), "Pista 1/2:\nA nudge");
+        assert_eq!(levels.describe_level(2), "Pista 2/2:\nThe answer");
+        assert_eq!(levels.describe_level(0), "");
+    }
+
+    #[test]
+    fn test_revealed_hint_level() {
+        let mut exercise = exercise("levels", "levels.cairo");
+        exercise.hint = Hint::Levels(vec!["A nudge".into(), "The answer".into()]);
+        let mut progress = Progress::default();
+        assert_eq!(exercise.hint_level_in(&progress), 0);
+
+        progress.set_hint_level(&exercise.path, 5);
+        assert_eq!(exercise.hint_level_in(&progress), 2);
+    }
+
+    #[test]
+    fn test_diagnostic_hints() {
+        let mut exercise = exercise("cairoFail", "tests/fixture/cairo/compileFail.cairo");
+        exercise.diagnostic_hints = vec![
+            DiagnosticHint {
+                pattern: Regex::new("Variable not found").unwrap(),
+                hint: "Declare it with `let`".into(),
+            },
+            DiagnosticHint {
+                pattern: Regex::new("^Run panicked").unwrap(),
+                hint: "Check the panic data".into(),
+            },
+        ];
+        let failed = anyhow::Error::new(CompilationFailed {
+            path: "compileFail.cairo".into(),
+            diagnostics: vec![Diagnostic {
+                file: "compileFail.cairo".into(),
+                start: None,
+                end: None,
+                severity: Severity::Error,
+                message: "Variable not found.".into(),
+            }],
+        });
 
-        assert_eq!(exercise.state(), State::Done);
+        assert_eq!(
+            exercise.diagnostic_hints_for(&failed),
+            vec!["Declare it with `let`"]
+        );
+        assert!(exercise
+            .diagnostic_hints_for(&anyhow::anyhow!("Gas consumido: 10"))
+            .is_empty());
     }
 }
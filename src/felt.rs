@@ -0,0 +1,82 @@
+//! Felts as written by hand, in `info.toml` and in test attributes.
+
+use cairo_felt::Felt;
+use cairo_lang_runner::short_string::as_cairo_short_string;
+use num_bigint::BigUint;
+use num_traits::Num;
+
+/// Parses a handwritten felt into its canonical representation.
+/// Values are written as decimal (`25`), hex (`0x19`), negative (`-1`)
+/// or short string (`'hello'`) felts.
+pub fn parse_felt(value: &str) -> Option<BigUint> {
+    let value = value.trim();
+    if let Some(short_string) = value.strip_prefix('\'').and_then(|v| v.strip_suffix('\'')) {
+        if short_string.len() > 31 {
+            return None;
+        }
+        return Some(BigUint::from_bytes_be(short_string.as_bytes()));
+    }
+    if let Some(negative) = value.strip_prefix('-') {
+        let prime = felt_prime();
+        return parse_felt(negative)
+            .filter(|v| *v <= prime)
+            .map(|v| (&prime - v) % &prime);
+    }
+    if let Some(hex) = value.strip_prefix("0x") {
+        return BigUint::from_str_radix(hex, 16).ok();
+    }
+    BigUint::from_str_radix(value, 10).ok()
+}
+
+/// The field prime: 2^251 + 17 * 2^192 + 1.
+pub fn felt_prime() -> BigUint {
+    (BigUint::from(1u32) << 251) + (BigUint::from(17u32) << 192) + BigUint::from(1u32)
+}
+
+/// Formats a felt, showing its short string representation when there is one.
+pub fn format_felt(value: &BigUint) -> String {
+    let bytes = value.to_bytes_be();
+    // `to_bytes_be` returns a single zero byte for zero, and never an empty vector.
+    let printable = bytes.len() <= 31
+        && bytes[0] != 0
+        && bytes
+            .iter()
+            .all(|byte| byte.is_ascii_graphic() || *byte == b' ');
+    if printable {
+        format!("{value} ('{}')", String::from_utf8_lossy(&bytes))
+    } else {
+        format!("{value}")
+    }
+}
+
+/// Formats felts, showing their short string representation when there is one.
+pub fn format_felts(values: &[Felt]) -> String {
+    let values = values
+        .iter()
+        .map(|value| match as_cairo_short_string(value) {
+            Some(as_string) => format!("{value} ('{as_string}')"),
+            None => format!("{value}"),
+        })
+        .collect::<Vec<_>>();
+    format!("[{}]", values.join(", "))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_parse_felt() {
+        assert_eq!(parse_felt("25"), Some(BigUint::from(25u32)));
+        assert_eq!(parse_felt("0x19"), Some(BigUint::from(25u32)));
+        assert_eq!(parse_felt("'a'"), Some(BigUint::from(97u32)));
+        assert_eq!(parse_felt("-1"), Some(felt_prime() - BigUint::from(1u32)));
+        assert_eq!(parse_felt("twenty"), None);
+    }
+
+    #[test]
+    fn test_format_felt() {
+        assert_eq!(format_felt(&BigUint::from(97u32)), "97 ('a')");
+        assert_eq!(format_felt(&BigUint::from(1u32)), "1");
+    }
+}
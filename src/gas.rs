@@ -0,0 +1,173 @@
+//! Gas accounting of Cairo runs and gas snapshots of tests.
+
+use std::collections::BTreeMap;
+use std::fmt::{self, Display, Formatter};
+use std::fs;
+use std::io;
+use std::path::Path;
+use std::str::FromStr;
+
+use anyhow::Context;
+use cairo_lang_runner::RunResult;
+use num_traits::ToPrimitive;
+
+/// The gas consumed by a run, if it was run with gas.
+/// The runner reports the gas left in the counter, not the gas spent.
+pub fn gas_used(available_gas: Option<usize>, result: &RunResult) -> Option<usize> {
+    let remaining = result.gas_counter.as_ref()?.to_biguint().to_usize()?;
+    available_gas?.checked_sub(remaining)
+}
+
+/// The gas used by each test, as saved in a `.gas-snapshot` file.
+/// Every line holds a test and its gas: `module::test_name (gas: 1234)`.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct GasSnapshot {
+    pub tests: BTreeMap<String, usize>,
+}
+
+impl GasSnapshot {
+    /// Builds a snapshot of the tests that were run with gas.
+    pub fn from_tests(gas_by_test: &[(String, Option<usize>)]) -> GasSnapshot {
+        GasSnapshot {
+            tests: gas_by_test
+                .iter()
+                .filter_map(|(name, gas_used)| Some((name.clone(), (*gas_used)?)))
+                .collect(),
+        }
+    }
+
+    /// Loads the snapshot file, treating a missing file as an empty snapshot.
+    pub fn load(path: &Path) -> anyhow::Result<GasSnapshot> {
+        let content = match fs::read_to_string(path) {
+            Ok(content) => content,
+            Err(error) if error.kind() == io::ErrorKind::NotFound => return Ok(Default::default()),
+            Err(error) => {
+                return Err(error).with_context(|| format!("Failed to read `{}`.", path.display()))
+            }
+        };
+        content.parse()
+    }
+
+    /// Writes the snapshot file, replacing the tests `covered` by this snapshot and
+    /// keeping the others already in it, such as the tests of other files.
+    pub fn save(&self, path: &Path, covered: impl Fn(&str) -> bool) -> anyhow::Result<()> {
+        let mut snapshot = GasSnapshot::load(path)?;
+        snapshot.tests.retain(|name, _| !covered(name));
+        snapshot.tests.extend(self.tests.clone());
+        fs::write(path, snapshot.to_string())
+            .with_context(|| format!("Failed to write `{}`.", path.display()))
+    }
+
+    /// Compares the gas of the tests in `actual` against this snapshot.
+    /// `tolerance` is the allowed difference, as a percentage of the snapshot gas.
+    /// The tests of this snapshot `covered` by `actual` but missing from it were
+    /// renamed or deleted, and are reported as well.
+    /// Returns a description of every test out of tolerance, `None` if there is none.
+    pub fn diff(
+        &self,
+        actual: &GasSnapshot,
+        covered: impl Fn(&str) -> bool,
+        tolerance: f64,
+    ) -> Option<String> {
+        let mut diff = vec![];
+        for name in self.tests.keys() {
+            if covered(name) && !actual.tests.contains_key(name) {
+                diff.push(format!("{name}: in the snapshot but not run"));
+            }
+        }
+        for (name, &gas_used) in &actual.tests {
+            let Some(&expected) = self.tests.get(name) else {
+                diff.push(format!("{name}: not in the snapshot (gas: {gas_used})"));
+                continue;
+            };
+            let change = gas_used as f64 - expected as f64;
+            if change.abs() > expected as f64 * tolerance / 100.0 {
+                diff.push(format!("{name}: {expected} -> {gas_used} ({change:+} gas)"));
+            }
+        }
+        if diff.is_empty() {
+            None
+        } else {
+            Some(diff.join("\n"))
+        }
+    }
+}
+
+impl Display for GasSnapshot {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        for (name, gas_used) in &self.tests {
+            writeln!(f, "{name} (gas: {gas_used})")?;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for GasSnapshot {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let tests = s
+            .lines()
+            .filter(|line| !line.trim().is_empty())
+            .map(|line| {
+                line.strip_suffix(')')
+                    .and_then(|line| line.rsplit_once(" (gas: "))
+                    .and_then(|(name, gas_used)| Some((name.to_string(), gas_used.parse().ok()?)))
+                    .with_context(|| format!("Invalid gas snapshot line: `{line}`"))
+            })
+            .collect::<anyhow::Result<_>>()?;
+        Ok(GasSnapshot { tests })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn snapshot(tests: &[(&str, usize)]) -> GasSnapshot {
+        GasSnapshot {
+            tests: tests
+                .iter()
+                .map(|(name, gas_used)| (name.to_string(), *gas_used))
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn test_snapshot_round_trip() {
+        let expected = snapshot(&[("tests::test_add", 1200), ("tests::test_sub", 980)]);
+
+        assert_eq!(
+            expected.to_string().parse::<GasSnapshot>().unwrap(),
+            expected
+        );
+    }
+
+    #[test]
+    fn test_snapshot_diff_tolerance() {
+        let expected = snapshot(&[("tests::test_add", 1000)]);
+
+        assert_eq!(
+            expected.diff(&snapshot(&[("tests::test_add", 1040)]), |_| true, 5.0),
+            None
+        );
+        assert!(expected
+            .diff(&snapshot(&[("tests::test_add", 1060)]), |_| true, 5.0)
+            .is_some());
+        assert!(expected
+            .diff(&snapshot(&[("tests::test_new", 10)]), |_| false, 5.0)
+            .is_some());
+    }
+
+    #[test]
+    fn test_snapshot_diff_missing_tests() {
+        let expected = snapshot(&[("tests::test_add", 1000), ("other::test_sub", 980)]);
+        let actual = snapshot(&[("tests::test_renamed", 1000)]);
+
+        let diff = expected
+            .diff(&actual, |name| name.starts_with("tests::"), 5.0)
+            .unwrap();
+        assert!(diff.contains("tests::test_add: in the snapshot but not run"));
+        assert!(!diff.contains("other::test_sub"));
+    }
+}
@@ -0,0 +1,8 @@
+//! Code shared by the `starklings` binary and the runner and tester binaries.
+
+pub mod capture;
+pub mod corelib;
+pub mod database;
+pub mod diagnostics;
+pub mod felt;
+pub mod gas;
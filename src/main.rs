@@ -1,30 +1,38 @@
-use crate::exercise::{Exercise, ExerciseList};
+use crate::exercise::{Exercise, ExerciseList, State};
+use crate::progress::Progress;
+use crate::report::{print_json, ExerciseReport, Format};
 use crate::project::RustAnalyzerProject;
-use crate::run::{reset, run};
-use crate::verify::verify;
+use crate::run::{diff, reset, run};
+use crate::verify::{verify, verify_json};
 use argh::FromArgs;
 use console::Emoji;
 use notify::DebouncedEvent;
 use notify::{RecommendedWatcher, RecursiveMode, Watcher};
+use starklings::corelib::CORELIB_ENV;
+use std::collections::HashSet;
 use std::ffi::OsStr;
 use std::fs;
 use std::io::{self, prelude::*};
+use std::iter;
 use std::path::{Path, PathBuf};
 use std::process::{Command, Stdio};
-use std::sync::atomic::{AtomicBool, Ordering};
-use std::sync::mpsc::{channel, RecvTimeoutError};
-use std::sync::{Arc, Mutex};
+use std::sync::mpsc::{channel, RecvTimeoutError, Sender, TryRecvError};
 use std::thread;
 use std::time::Duration;
 
 #[macro_use]
 mod ui;
+mod contract;
 mod exercise;
+mod output;
+mod progress;
 mod project;
+mod report;
 mod run;
 mod starklings_runner;
 mod starklings_tester;
 mod verify;
+mod watch_ui;
 
 // In sync with crate version
 const VERSION: &str = "5.3.0";
@@ -38,6 +46,12 @@ struct Args {
     /// show the executable version
     #[argh(switch, short = 'v')]
     version: bool,
+    /// output format of `list`, `verify` and `run`: human (default) or json
+    #[argh(option, default = "Format::Human")]
+    format: Format,
+    /// path to the Cairo corelib, otherwise looked up as corelib::init_corelib describes
+    #[argh(option)]
+    corelib: Option<PathBuf>,
     #[argh(subcommand)]
     nested: Option<Subcommands>,
 }
@@ -64,7 +78,11 @@ struct VerifyArgs {}
 #[derive(FromArgs, PartialEq, Debug)]
 #[argh(subcommand, name = "watch")]
 /// Reruns `verify` when files were edited
-struct WatchArgs {}
+struct WatchArgs {
+    /// show a full-screen interface with the list of exercises
+    #[argh(switch)]
+    tui: bool,
+}
 
 #[derive(FromArgs, PartialEq, Debug)]
 #[argh(subcommand, name = "compile_solutions")]
@@ -78,6 +96,9 @@ struct RunArgs {
     #[argh(positional)]
     /// the name of the exercise
     name: String,
+    #[argh(positional)]
+    /// the arguments passed to `main`, after `--`, instead of those in info.toml
+    args: Vec<String>,
 }
 
 #[derive(FromArgs, PartialEq, Debug)]
@@ -142,6 +163,10 @@ fn main() {
         println!("\n{WELCOME}\n");
     }
 
+    if let Some(corelib) = &args.corelib {
+        std::env::set_var(CORELIB_ENV, corelib);
+    }
+
     if !Path::new("info.toml").exists() {
         println!(
             "{} debe ejecutarse desde el directorio starklings",
@@ -158,6 +183,11 @@ fn main() {
         std::process::exit(1);
     }
 
+    if args.format == Format::Json {
+        console::set_colors_enabled(false);
+        colored::control::set_override(false);
+    }
+
     let toml_str = &fs::read_to_string("info.toml").unwrap();
     let mut exercises = toml::from_str::<ExerciseList>(toml_str).unwrap().exercises;
     let command = args.nested.unwrap_or_else(|| {
@@ -166,58 +196,7 @@ fn main() {
     });
     match command {
         Subcommands::List(subargs) => {
-            if !subargs.paths && !subargs.names {
-                println!("{:<17}\t{:<46}\t{:<7}", "Name", "Path", "Status");
-            }
-            let mut exercises_done: u16 = 0;
-            let filters = subargs.filter.clone().unwrap_or_default().to_lowercase();
-            exercises.iter().for_each(|e| {
-                let fname = format!("{}", e.path.display());
-                let filter_cond = filters
-                    .split(',')
-                    .filter(|f| !f.trim().is_empty())
-                    .any(|f| e.name.contains(f) || fname.contains(f));
-                let status = if e.looks_done() {
-                    exercises_done += 1;
-                    "Hecho"
-                } else {
-                    "Pendiente"
-                };
-                let solve_cond = {
-                    (e.looks_done() && subargs.solved)
-                        || (!e.looks_done() && subargs.unsolved)
-                        || (!subargs.solved && !subargs.unsolved)
-                };
-                if solve_cond && (filter_cond || subargs.filter.is_none()) {
-                    let line = if subargs.paths {
-                        format!("{fname}\n")
-                    } else if subargs.names {
-                        format!("{}\n", e.name)
-                    } else {
-                        format!("{:<17}\t{fname:<46}\t{status:<7}\n", e.name)
-                    };
-                    // Somehow using println! leads to the binary panicking
-                    // when its output is piped.
-                    // So, we're handling a Broken Pipe error and exiting with 0 anyway
-                    let stdout = std::io::stdout();
-                    {
-                        let mut handle = stdout.lock();
-                        handle.write_all(line.as_bytes()).unwrap_or_else(|e| {
-                            match e.kind() {
-                                std::io::ErrorKind::BrokenPipe => std::process::exit(0),
-                                _ => std::process::exit(1),
-                            };
-                        });
-                    }
-                }
-            });
-            let percentage_progress = exercises_done as f32 / exercises.len() as f32 * 100.0;
-            println!(
-                "Progreso: Has completado {} / {} ejercicios ({:.1} %).",
-                exercises_done,
-                exercises.len(),
-                percentage_progress
-            );
+            list(&exercises, &subargs, args.format == Format::Json);
             std::process::exit(0);
         }
 
@@ -229,9 +208,20 @@ fn main() {
         }
 
         Subcommands::Run(subargs) => {
-            let exercise = find_exercise(&subargs.name, &exercises);
+            let mut exercise = find_exercise(&subargs.name, &exercises).clone();
+            if !subargs.args.is_empty() {
+                exercise.args = subargs.args;
+            }
 
-            run(exercise).unwrap_or_else(|_| std::process::exit(1));
+            if args.format == Format::Json {
+                let report = ExerciseReport::run(&exercise);
+                print_json(&report);
+                if !report.succeeded() {
+                    std::process::exit(1);
+                }
+            } else {
+                run(&exercise).unwrap_or_else(|_| std::process::exit(1));
+            }
         }
 
         Subcommands::Reset(subargs) => {
@@ -243,11 +233,15 @@ fn main() {
         Subcommands::Hint(subargs) => {
             let exercise = find_exercise(&subargs.name, &exercises);
 
-            println!("{}", exercise.hint);
+            hint(exercise);
         }
 
         Subcommands::Verify(_subargs) => {
-            verify(&exercises, (0, exercises.len())).unwrap_or_else(|_| std::process::exit(1));
+            if args.format == Format::Json {
+                verify_json(&exercises).unwrap_or_else(|_| std::process::exit(1));
+            } else {
+                verify(&exercises, (0, exercises.len())).unwrap_or_else(|_| std::process::exit(1));
+            }
         }
 
         Subcommands::Lsp(_subargs) => {
@@ -292,6 +286,13 @@ fn main() {
             }
         }
 
+        Subcommands::Watch(subargs) if subargs.tui => {
+            if let Err(e) = watch_ui::watch(&exercises) {
+                println!("Error: {e:?}");
+                std::process::exit(1);
+            }
+        }
+
         Subcommands::Watch(_subargs) => match watch(&exercises) {
             Err(e) => {
                 println!(
@@ -315,37 +316,173 @@ fn main() {
     }
 }
 
-fn spawn_watch_shell(
-    failed_exercise_hint: &Arc<Mutex<Option<String>>>,
-    should_quit: Arc<AtomicBool>,
-) {
-    let failed_exercise_hint = Arc::clone(failed_exercise_hint);
+// Reveals the next level of the hint of an exercise and prints it
+fn hint(exercise: &Exercise) {
+    let (level, recorded) = exercise.reveal_hint();
+    println!("{}", exercise.hint.describe_level(level));
+    if let Err(error) = recorded {
+        warn!("No se pudo guardar la pista revelada: {}", error);
+    }
+}
+
+// Print the exercises matching the filters of `list`, with their state
+fn list(exercises: &[Exercise], subargs: &ListArgs, json: bool) {
+    if !subargs.paths && !subargs.names && !json {
+        println!(
+            "{:<17}\t{:<46}\t{:<10}\t{}",
+            "Name", "Path", "Status", "Hints"
+        );
+    }
+    let mut exercises_done: u16 = 0;
+    let filters = subargs.filter.clone().unwrap_or_default().to_lowercase();
+    let progress = Progress::load();
+    let mut reports = vec![];
+    exercises.iter().for_each(|e| {
+        let fname = format!("{}", e.path.display());
+        let filter_cond = filters
+            .split(',')
+            .filter(|f| !f.trim().is_empty())
+            .any(|f| e.name.contains(f) || fname.contains(f));
+        let state = e.state_in(&progress);
+        let status = match state {
+            State::Done => {
+                exercises_done += 1;
+                "Hecho"
+            }
+            State::Modified => "Modificado",
+            State::Pending => "Pendiente",
+        };
+        let is_done = state == State::Done;
+        let solve_cond = {
+            (is_done && subargs.solved)
+                || (!is_done && subargs.unsolved)
+                || (!subargs.solved && !subargs.unsolved)
+        };
+        if solve_cond && (filter_cond || subargs.filter.is_none()) {
+            if json {
+                reports.push(ExerciseReport::new(e, state));
+                return;
+            }
+            let line = if subargs.paths {
+                format!("{fname}\n")
+            } else if subargs.names {
+                format!("{}\n", e.name)
+            } else {
+                let hints = format!("{}/{}", e.hint_level_in(&progress), e.hint.levels().len());
+                format!("{:<17}\t{fname:<46}\t{status:<10}\t{hints}\n", e.name)
+            };
+            // Somehow using println! leads to the binary panicking
+            // when its output is piped.
+            // So, we're handling a Broken Pipe error and exiting with 0 anyway
+            let stdout = std::io::stdout();
+            {
+                let mut handle = stdout.lock();
+                handle.write_all(line.as_bytes()).unwrap_or_else(|e| {
+                    match e.kind() {
+                        std::io::ErrorKind::BrokenPipe => std::process::exit(0),
+                        _ => std::process::exit(1),
+                    };
+                });
+            }
+        }
+    });
+    if json {
+        print_json(&reports);
+        return;
+    }
+    let percentage_progress = exercises_done as f32 / exercises.len() as f32 * 100.0;
+    println!(
+        "Progreso: Has completado {} / {} ejercicios ({:.1} %).",
+        exercises_done,
+        exercises.len(),
+        percentage_progress
+    );
+}
+
+// The commands typed in the watch shell that act on the watched exercises.
+// The exercise names default to the current exercise
+enum ShellCommand {
+    Hint,
+    Next,
+    Prev,
+    Goto(String),
+    Run(Option<String>),
+    Reset(Option<String>),
+    List(Option<String>),
+    Verify,
+    Skip,
+    Diff(Option<String>),
+    Quit,
+}
+
+fn spawn_watch_shell(commands: Sender<ShellCommand>) {
     println!("¡Bienvenido al modo watch! Puedes escribir 'help' para obtener una visión general de los comandos que puedes utilizar aquí.");
     thread::spawn(move || loop {
         let mut input = String::new();
         match io::stdin().read_line(&mut input) {
+            // stdin was closed, there are no more commands to read
+            Ok(0) => return,
             Ok(_) => {
                 let input = input.trim();
-                if input == "hint" {
-                    if let Some(hint) = &*failed_exercise_hint.lock().unwrap() {
-                        println!("{hint}");
+                let (command, argument) = match input.split_once(' ') {
+                    Some((command, argument)) => (command, argument.trim()),
+                    None => (input, ""),
+                };
+                let argument = (!argument.is_empty()).then(|| argument.to_string());
+                let command = match command {
+                    "hint" => ShellCommand::Hint,
+                    "next" => ShellCommand::Next,
+                    "prev" => ShellCommand::Prev,
+                    "goto" => match argument {
+                        Some(name) => ShellCommand::Goto(name),
+                        None => {
+                            println!("uso: goto <nombre del ejercicio>");
+                            continue;
+                        }
+                    },
+                    "run" => ShellCommand::Run(argument),
+                    "reset" => ShellCommand::Reset(argument),
+                    "list" => ShellCommand::List(argument),
+                    "verify" => ShellCommand::Verify,
+                    "skip" => ShellCommand::Skip,
+                    "diff" => ShellCommand::Diff(argument),
+                    "quit" => {
+                        println!("Bye!");
+                        ShellCommand::Quit
                     }
-                } else if input == "clear" {
-                    println!("\x1B[2J\x1B[1;1H");
-                } else if input.eq("quit") {
-                    should_quit.store(true, Ordering::SeqCst);
-                    println!("Bye!");
-                } else if input.eq("help") {
-                    println!("Comandos disponibles en modo watch:");
-                    println!("  hint  - imprime la pista del ejercicio actual");
-                    println!("  clear - limpia la pantalla");
-                    println!("  quit  - quita modo watch");
-                    println!("  help  - muestra este mensaje de ayuda");
-                    println!();
-                    println!("El modo Watch reevalúa automáticamente el ejercicio en curso");
-                    println!("cuando edite el contenido de un archivo.")
-                } else {
-                    println!("unknown command: {input}");
+                    "clear" => {
+                        println!("\x1B[2J\x1B[1;1H");
+                        continue;
+                    }
+                    "help" => {
+                        println!("Comandos disponibles en modo watch:");
+                        println!("  hint           - imprime la pista del ejercicio actual");
+                        println!("  next           - pasa al ejercicio siguiente");
+                        println!("  prev           - vuelve al ejercicio anterior");
+                        println!("  goto <nombre>  - pasa al ejercicio con ese nombre");
+                        println!("  run [nombre]   - ejecuta el ejercicio actual o el indicado");
+                        println!("  reset [nombre] - deshace los cambios del ejercicio");
+                        println!("  diff [nombre]  - muestra los cambios hechos al ejercicio");
+                        println!("  list [filtro]  - lista los ejercicios y su estado");
+                        println!("  verify         - verifica todos los ejercicios en orden");
+                        println!("  skip           - deja el ejercicio actual para más tarde");
+                        println!("  clear          - limpia la pantalla");
+                        println!("  quit           - quita modo watch");
+                        println!("  help           - muestra este mensaje de ayuda");
+                        println!();
+                        println!("El modo Watch reevalúa automáticamente el ejercicio en curso");
+                        println!("cuando edite el contenido de un archivo. Los demás ejercicios");
+                        println!("que guarde se evalúan por separado, sin cambiar el actual.");
+                        continue;
+                    }
+                    _ => {
+                        println!("unknown command: {input}");
+                        continue;
+                    }
+                };
+                let quit = matches!(command, ShellCommand::Quit);
+                if commands.send(command).is_err() || quit {
+                    return;
                 }
             }
             Err(error) => println!("error leyendo comando: {error}"),
@@ -355,9 +492,10 @@ fn spawn_watch_shell(
 
 fn find_exercise<'a>(name: &str, exercises: &'a [Exercise]) -> &'a Exercise {
     if name.eq("siguiente") {
+        let recorded = Progress::load();
         exercises
             .iter()
-            .find(|e| !e.looks_done())
+            .find(|e| e.state_in(&recorded) != State::Done)
             .unwrap_or_else(|| {
                 println!("🎉 ¡Enhorabuena! ¡Has hecho todos los ejercicios!");
                 println!("🔚 ¡No hay más ejercicios que hacer a continuación!");
@@ -387,59 +525,205 @@ fn watch(exercises: &[Exercise]) -> notify::Result<WatchStatus> {
     }
 
     let (tx, rx) = channel();
-    let should_quit = Arc::new(AtomicBool::new(false));
+    let (commands_tx, commands) = channel();
 
     let mut watcher: RecommendedWatcher = Watcher::new(tx, Duration::from_secs(2))?;
     watcher.watch(Path::new("./exercises"), RecursiveMode::Recursive)?;
 
     clear_screen();
 
-    let to_owned_hint = |t: &Exercise| t.hint.to_owned();
-    let failed_exercise_hint = match verify(exercises.iter(), (0, exercises.len())) {
-        Ok(_) => return Ok(WatchStatus::Finished),
-        Err(exercise) => Arc::new(Mutex::new(Some(to_owned_hint(exercise)))),
+    // The exercise the learner is working on, which the hint and the
+    // `next`/`prev` commands refer to
+    let recorded = Progress::load();
+    let Some(mut current) = exercises
+        .iter()
+        .position(|e| e.state_in(&recorded) != State::Done)
+    else {
+        return Ok(WatchStatus::Finished);
     };
-    spawn_watch_shell(&failed_exercise_hint, Arc::clone(&should_quit));
+    // The exercises left for later with `skip`
+    let mut skipped = HashSet::new();
+    if verify_current(exercises, &mut current, &skipped) {
+        return Ok(WatchStatus::Finished);
+    }
+    spawn_watch_shell(commands_tx);
     loop {
-        match rx.recv_timeout(Duration::from_secs(1)) {
+        match rx.recv_timeout(Duration::from_millis(200)) {
             Ok(event) => match event {
                 DebouncedEvent::Create(b) | DebouncedEvent::Chmod(b) | DebouncedEvent::Write(b) => {
                     if b.extension() == Some(OsStr::new("cairo")) && b.exists() {
                         let filepath = b.as_path().canonicalize().unwrap();
-                        let pending_exercises = exercises
-                            .iter()
-                            .find(|e| filepath.ends_with(&e.path))
-                            .into_iter()
-                            .chain(
-                                exercises
-                                    .iter()
-                                    .filter(|e| !e.looks_done() && !filepath.ends_with(&e.path)),
-                            );
-                        let num_done = exercises.iter().filter(|e| e.looks_done()).count();
-                        clear_screen();
-                        match verify(pending_exercises, (num_done, exercises.len())) {
-                            Ok(_) => return Ok(WatchStatus::Finished),
-                            Err(exercise) => {
-                                let mut failed_exercise_hint = failed_exercise_hint.lock().unwrap();
-                                *failed_exercise_hint = Some(to_owned_hint(exercise));
+                        match exercises.iter().position(|e| filepath.ends_with(&e.path)) {
+                            Some(edited) if edited == current => {
+                                clear_screen();
+                                if verify_current(exercises, &mut current, &skipped) {
+                                    return Ok(WatchStatus::Finished);
+                                }
                             }
+                            Some(edited) => {
+                                let exercise = &exercises[edited];
+                                println!();
+                                println!(
+                                    "Has guardado {exercise}, que no es el ejercicio actual ({}):",
+                                    exercises[current]
+                                );
+                                let done = progress(exercises, &Progress::load());
+                                let _ = verify(iter::once(exercise), done);
+                            }
+                            None => {}
                         }
                     }
                 }
                 _ => {}
             },
             Err(RecvTimeoutError::Timeout) => {
-                // the timeout expired, just check the `should_quit` variable below then loop again
+                // the timeout expired, just check the shell commands below then loop again
             }
             Err(e) => println!("watch error: {e:?}"),
         }
-        // Check if we need to exit
-        if should_quit.load(Ordering::SeqCst) {
-            return Ok(WatchStatus::Unfinished);
+        let command = match commands.try_recv() {
+            Ok(command) => command,
+            Err(TryRecvError::Empty) | Err(TryRecvError::Disconnected) => continue,
+        };
+        // The exercise a command refers to, the current one when no name was given
+        let find = move |name: Option<String>| match name {
+            None => Some(current),
+            Some(name) => {
+                let found = exercises.iter().position(|e| e.name == name);
+                if found.is_none() {
+                    println!("No se encontró ningún ejercicio para '{name}'!");
+                }
+                found
+            }
+        };
+        let focused = match command {
+            ShellCommand::Hint => {
+                hint(&exercises[current]);
+                None
+            }
+            ShellCommand::Next if current + 1 < exercises.len() => Some(current + 1),
+            ShellCommand::Prev if current > 0 => Some(current - 1),
+            ShellCommand::Next | ShellCommand::Prev => {
+                println!("No hay más ejercicios en esa dirección.");
+                None
+            }
+            ShellCommand::Goto(name) => find(Some(name)),
+            ShellCommand::Run(name) => {
+                if let Some(index) = find(name) {
+                    let _ = run(&exercises[index]);
+                }
+                None
+            }
+            ShellCommand::Reset(name) => {
+                if let Some(index) = find(name) {
+                    match reset(&exercises[index]) {
+                        Ok(()) => println!("Se han deshecho los cambios de {}.", exercises[index]),
+                        Err(()) => println!("No se pudo restablecer {}.", exercises[index]),
+                    }
+                }
+                None
+            }
+            ShellCommand::Diff(name) => {
+                if let Some(index) = find(name) {
+                    if diff(&exercises[index]).is_err() {
+                        println!(
+                            "No se pudieron mostrar los cambios de {}.",
+                            exercises[index]
+                        );
+                    }
+                }
+                None
+            }
+            ShellCommand::List(filter) => {
+                let subargs = ListArgs {
+                    paths: false,
+                    names: false,
+                    filter,
+                    unsolved: false,
+                    solved: false,
+                };
+                list(exercises, &subargs, false);
+                None
+            }
+            ShellCommand::Verify => match verify(exercises, (0, exercises.len())) {
+                Ok(()) => return Ok(WatchStatus::Finished),
+                Err(failed) => {
+                    current = exercises
+                        .iter()
+                        .position(|e| e.name == failed.name)
+                        .unwrap();
+                    None
+                }
+            },
+            ShellCommand::Skip => {
+                skipped.insert(current);
+                match next_pending(exercises, &Progress::load(), current, &skipped) {
+                    Some(pending) => {
+                        current = pending;
+                        clear_screen();
+                        if verify_current(exercises, &mut current, &skipped) {
+                            return Ok(WatchStatus::Finished);
+                        }
+                    }
+                    None => println!("No hay otros ejercicios pendientes."),
+                }
+                None
+            }
+            ShellCommand::Quit => return Ok(WatchStatus::Unfinished),
+        };
+        if let Some(focused) = focused {
+            // Moving to an exercise only verifies it, without moving on when it passes
+            current = focused;
+            clear_screen();
+            let done = progress(exercises, &Progress::load());
+            let _ = verify(iter::once(&exercises[current]), done);
+        }
+    }
+}
+
+// Verify the current exercise and, while it passes, move on to the next pending one.
+// Returns whether all the exercises are done
+fn verify_current(exercises: &[Exercise], current: &mut usize, skipped: &HashSet<usize>) -> bool {
+    loop {
+        // The current exercise is left out by `next_pending`, so the progress
+        // recorded before verifying it is enough to find the next one
+        let recorded = Progress::load();
+        let done = progress(exercises, &recorded);
+        if verify(iter::once(&exercises[*current]), done).is_err() {
+            return false;
+        }
+        match next_pending(exercises, &recorded, *current, skipped) {
+            Some(pending) => *current = pending,
+            None => return true,
         }
     }
 }
 
+// The pending exercise after the current one, wrapping around.
+// Skipped exercises come up again once the others are done
+fn next_pending(
+    exercises: &[Exercise],
+    progress: &Progress,
+    current: usize,
+    skipped: &HashSet<usize>,
+) -> Option<usize> {
+    let pending = |i: &usize| exercises[*i].state_in(progress) != State::Done;
+    let others = (current + 1..exercises.len()).chain(0..current);
+    others
+        .clone()
+        .find(|i| !skipped.contains(i) && pending(i))
+        .or_else(|| others.into_iter().find(pending))
+}
+
+// The number of exercises done and the total, as shown by the progress bar of `verify`
+fn progress(exercises: &[Exercise], recorded: &Progress) -> (usize, usize) {
+    let num_done = exercises
+        .iter()
+        .filter(|e| e.state_in(recorded) == State::Done)
+        .count();
+    (num_done, exercises.len())
+}
+
 fn rustc_exists() -> bool {
     Command::new("rustc")
         .args(["--version"])
@@ -476,8 +760,8 @@ un editor y comienza tu trabajo de detective.
 3. Si estás atascado en un ejercicio, hay una pista útil que puedes ver 
 escribiendo `hint` (en modo watch), o ejecutando `cargo run --bin starklings hint
  nombre_del_ejercicio`.
-4. Cuando hayas resuelto el ejercicio con éxito, elimina el comentario
-`// I AM NOT DONE` para pasar al siguiente ejercicio.
+4. Cuando hayas resuelto el ejercicio con éxito, starklings guardará tu progreso
+y pasará automáticamente al siguiente ejercicio.
 5. Si un ejercicio no tiene sentido para ti, ¡por favor abre un problema en GitHub!
 (https://github.com/shramee/starklings-cairo1/issues/new).
 
@@ -531,3 +815,69 @@ pub const WELCOME: &str = r#"Starklings - Un tutorial interactivo para aprender
   |___/\__\__,_|_|  |_|\_\_|_|_| |_|\__, |___/
                                      __/ |
                                     |___/"#;
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::progress::source_hash;
+
+    fn exercises() -> Vec<Exercise> {
+        let toml_str = ["compilePass", "compileFail", "testPass", "testFails"]
+            .iter()
+            .map(|name| {
+                format!(
+                    "[[exercises]]\n\
+                    name = \"{name}\"\n\
+                    path = \"tests/fixture/cairo/{name}.cairo\"\n\
+                    mode = \"compile\"\n\
+                    hint = \"\"\n"
+                )
+            })
+            .collect::<String>();
+        toml::from_str::<ExerciseList>(&toml_str).unwrap().exercises
+    }
+
+    fn done(exercises: &[Exercise], indexes: &[usize]) -> Progress {
+        let mut progress = Progress::default();
+        for index in indexes {
+            let path = &exercises[*index].path;
+            progress.mark_verified(path, source_hash(path).unwrap());
+        }
+        progress
+    }
+
+    #[test]
+    fn test_next_pending_wraps_around() {
+        let exercises = exercises();
+        let progress = done(&exercises, &[1, 3]);
+
+        assert_eq!(
+            next_pending(&exercises, &progress, 2, &HashSet::new()),
+            Some(0)
+        );
+        assert_eq!(
+            next_pending(&exercises, &progress, 0, &HashSet::new()),
+            Some(2)
+        );
+    }
+
+    #[test]
+    fn test_next_pending_prefers_exercises_not_skipped() {
+        let exercises = exercises();
+        let progress = done(&exercises, &[3]);
+        let skipped = HashSet::from([1]);
+
+        assert_eq!(next_pending(&exercises, &progress, 0, &skipped), Some(2));
+    }
+
+    #[test]
+    fn test_next_pending_falls_back_to_skipped_exercises() {
+        let exercises = exercises();
+        let progress = done(&exercises, &[2, 3]);
+        let skipped = HashSet::from([1]);
+
+        assert_eq!(next_pending(&exercises, &progress, 0, &skipped), Some(1));
+        let all_done = done(&exercises, &[1, 2, 3]);
+        assert_eq!(next_pending(&exercises, &all_done, 0, &skipped), None);
+    }
+}
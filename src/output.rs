@@ -0,0 +1,159 @@
+//! Expected results of `output` exercises, as declared in `info.toml`.
+
+use std::fmt::{self, Display, Formatter};
+
+use cairo_felt::Felt;
+use cairo_lang_runner::RunResultValue;
+use console::style;
+use num_bigint::BigUint;
+use serde::Deserialize;
+use starklings::capture::PrintedFelt;
+use starklings::felt::{format_felts, parse_felt};
+
+/// The result `main` must produce for an exercise to be considered solved.
+/// Values are written as handwritten felts, as parsed by [`parse_felt`].
+#[derive(Deserialize, Clone, Debug, Default, PartialEq, Eq)]
+pub struct ExpectedOutput {
+    /// The values returned by `main`.
+    pub returns: Option<Vec<String>>,
+    /// The panic data `main` should panic with.
+    pub panic: Option<Vec<String>>,
+    /// The felts `main` should print with `debug::print`, in order.
+    pub printed: Option<Vec<String>>,
+}
+
+/// The error returned when a program ran but its output was not the expected one.
+#[derive(Debug)]
+pub struct OutputMismatch {
+    pub diff: String,
+}
+
+impl Display for OutputMismatch {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "{}", self.diff)
+    }
+}
+
+impl std::error::Error for OutputMismatch {}
+
+impl ExpectedOutput {
+    /// Compares the result of a run and the felts it printed against the expected output.
+    pub fn check(
+        &self,
+        result: &RunResultValue,
+        printed: &[PrintedFelt],
+    ) -> Result<(), OutputMismatch> {
+        let mut diff = vec![];
+        match result {
+            RunResultValue::Success(values) => {
+                if let Some(expected) = &self.panic {
+                    diff.push(format!(
+                        "se esperaba un panic con {}, pero devolvió {}",
+                        format_expected(expected),
+                        format_felts(values)
+                    ));
+                } else if let Some(expected) = &self.returns {
+                    compare_felts("devuelto", expected, values, &mut diff);
+                }
+            }
+            RunResultValue::Panic(values) => {
+                if let Some(expected) = &self.panic {
+                    compare_felts("panic", expected, values, &mut diff);
+                } else {
+                    diff.push(format!(
+                        "no se esperaba un panic, pero falló con {}",
+                        format_felts(values)
+                    ));
+                }
+            }
+        }
+        if let Some(expected) = &self.printed {
+            let actual = printed
+                .iter()
+                .map(|felt| felt.value.parse().ok())
+                .collect::<Vec<_>>();
+            let formatted = printed.iter().map(format_printed).collect::<Vec<_>>();
+            compare(
+                "impreso",
+                expected,
+                &actual,
+                format!("[{}]", formatted.join(", ")),
+                &mut diff,
+            );
+        }
+        if diff.is_empty() {
+            Ok(())
+        } else {
+            Err(OutputMismatch {
+                diff: diff.join("\n"),
+            })
+        }
+    }
+}
+
+/// Compares a list of expected values with the actual felts, pushing a diff when they differ.
+fn compare_felts(what: &str, expected: &[String], actual: &[Felt], diff: &mut Vec<String>) {
+    let values = actual
+        .iter()
+        .map(|value| Some(value.to_biguint()))
+        .collect::<Vec<_>>();
+    compare(what, expected, &values, format_felts(actual), diff);
+}
+
+/// Compares a list of expected values with the actual ones, formatted as `formatted`,
+/// pushing a diff when they differ. Actual values that could not be read are `None`.
+fn compare(
+    what: &str,
+    expected: &[String],
+    actual: &[Option<BigUint>],
+    formatted: String,
+    diff: &mut Vec<String>,
+) {
+    let matches = expected.len() == actual.len()
+        && expected
+            .iter()
+            .zip(actual)
+            .all(|(e, a)| a.is_some() && parse_felt(e) == *a);
+    if !matches {
+        diff.push(format!("Valor {what} incorrecto:"));
+        diff.push(format!(
+            "{}",
+            style(format!("- esperado: {}", format_expected(expected))).red()
+        ));
+        diff.push(format!(
+            "{}",
+            style(format!("+ obtenido: {formatted}")).green()
+        ));
+    }
+}
+
+/// Formats a printed felt like `format_felts` does.
+fn format_printed(felt: &PrintedFelt) -> String {
+    match &felt.text {
+        Some(text) => format!("{} ('{text}')", felt.value),
+        None => felt.value.clone(),
+    }
+}
+
+/// Describes the result of a successful run of `main`.
+pub fn describe_run_result(value: &RunResultValue) -> String {
+    match value {
+        RunResultValue::Success(values) => format!(
+            "Ejecución completada con éxito, devolviendo {}",
+            format_felts(values)
+        ),
+        RunResultValue::Panic(values) => format!("Panic esperado con {}", format_felts(values)),
+    }
+}
+
+/// Describes the gas consumed by a run, along with the budget of the exercise.
+pub fn describe_gas(gas_used: usize, max_gas: Option<usize>) -> String {
+    match max_gas {
+        Some(max_gas) => format!("Gas consumido: {gas_used} (máximo {max_gas})"),
+        None => format!("Gas consumido: {gas_used}"),
+    }
+}
+
+fn format_expected(values: &[String]) -> String {
+    format!("[{}]", values.join(", "))
+}
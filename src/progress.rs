@@ -0,0 +1,109 @@
+//! Persisted progress of the learner, stored in `.starklings/progress.json`.
+//! An exercise is done when the source that last passed `verify` is still
+//! the source on disk. Exercises are keyed by their path, so checking the
+//! solutions with `compile_solutions` does not touch the progress of the
+//! exercises themselves.
+
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::BTreeMap;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+const PROGRESS_FILE: &str = ".starklings/progress.json";
+
+/// The progress made on every exercise, keyed by the path of its source.
+#[derive(Serialize, Deserialize, Default, Debug)]
+pub struct Progress {
+    #[serde(default)]
+    exercises: BTreeMap<String, ExerciseProgress>,
+}
+
+/// The progress made on a single exercise.
+#[derive(Serialize, Deserialize, Default, Clone, Debug)]
+pub struct ExerciseProgress {
+    /// The hash of the source that last passed `verify`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub verified_hash: Option<String>,
+    /// How many levels of the hint have been revealed.
+    #[serde(default, skip_serializing_if = "is_zero")]
+    pub hint_level: usize,
+}
+
+impl Progress {
+    /// Loads the progress from the default location.
+    /// A missing or unreadable file is treated as no progress at all.
+    pub fn load() -> Progress {
+        Progress::load_from(Path::new(PROGRESS_FILE))
+    }
+
+    pub fn load_from(path: &Path) -> Progress {
+        fs::read_to_string(path)
+            .ok()
+            .and_then(|content| serde_json::from_str(&content).ok())
+            .unwrap_or_default()
+    }
+
+    /// Saves the progress to the default location.
+    pub fn save(&self) -> io::Result<()> {
+        self.save_to(Path::new(PROGRESS_FILE))
+    }
+
+    pub fn save_to(&self, path: &Path) -> io::Result<()> {
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        fs::write(
+            path,
+            serde_json::to_vec_pretty(&self).expect("Failed to serialize to JSON"),
+        )
+    }
+
+    /// The hash of the source that last passed `verify` for the given exercise.
+    pub fn verified_hash(&self, path: &Path) -> Option<&str> {
+        self.exercises
+            .get(&key(path))
+            .and_then(|exercise| exercise.verified_hash.as_deref())
+    }
+
+    /// Records that the given source hash passed `verify`.
+    pub fn mark_verified(&mut self, path: &Path, hash: String) {
+        self.exercises.entry(key(path)).or_default().verified_hash = Some(hash);
+    }
+
+    /// How many levels of the hint of the given exercise have been revealed.
+    pub fn hint_level(&self, path: &Path) -> usize {
+        self.exercises
+            .get(&key(path))
+            .map_or(0, |exercise| exercise.hint_level)
+    }
+
+    /// Records how many levels of the hint of an exercise have been revealed.
+    pub fn set_hint_level(&mut self, path: &Path, level: usize) {
+        self.exercises.entry(key(path)).or_default().hint_level = level;
+    }
+
+    /// Forgets the verified source of an exercise.
+    /// Returns whether the exercise had been verified before.
+    pub fn clear_verified(&mut self, path: &Path) -> bool {
+        self.exercises
+            .get_mut(&key(path))
+            .and_then(|exercise| exercise.verified_hash.take())
+            .is_some()
+    }
+}
+
+fn key(path: &Path) -> String {
+    path.to_string_lossy().into_owned()
+}
+
+fn is_zero(level: &usize) -> bool {
+    *level == 0
+}
+
+/// Computes the hash identifying the current source of an exercise.
+pub fn source_hash(path: &Path) -> io::Result<String> {
+    let digest = Sha256::digest(fs::read(path)?);
+    Ok(digest.iter().map(|byte| format!("{byte:02x}")).collect())
+}
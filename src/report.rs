@@ -0,0 +1,229 @@
+//! Machine-readable reports of exercises, printed with `--format json`.
+
+use std::str::FromStr;
+
+use cairo_felt::Felt;
+use cairo_lang_runner::RunResultValue;
+use serde::Serialize;
+use starklings::capture::PrintedFelt;
+use starklings::diagnostics::{CompilationFailed, Diagnostic};
+
+use crate::exercise::{Exercise, GasBudgetExceeded, Mode, State};
+use crate::output::describe_run_result;
+use crate::starklings_runner::ProgramPanicked;
+use crate::starklings_tester::TestsSummary;
+
+/// The output format of the commands.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Format {
+    /// Colored text meant to be read by the learner.
+    Human,
+    /// JSON meant to be read by dashboards and editor plugins.
+    Json,
+}
+
+impl FromStr for Format {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "human" => Ok(Format::Human),
+            "json" => Ok(Format::Json),
+            _ => Err(format!(
+                "formato desconocido `{s}`, se esperaba `human` o `json`"
+            )),
+        }
+    }
+}
+
+/// The report of a single exercise.
+#[derive(Serialize, Debug)]
+pub struct ExerciseReport<'a> {
+    pub name: &'a str,
+    pub path: String,
+    pub mode: Mode,
+    pub state: State,
+    /// Whether the exercise passed, only set when it was run.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub success: Option<bool>,
+    /// The output of a successful run.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub output: Option<String>,
+    /// The error of a failed run.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    /// The compiler diagnostics, when the exercise does not compile.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub diagnostics: Option<Vec<Diagnostic>>,
+    /// The values `main` returned or panicked with.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub run_result: Option<RunReport>,
+    /// The gas consumed by `main`, or by all the tests together.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub gas_used: Option<usize>,
+    /// The felts `main` printed with `debug::print`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub printed: Option<Vec<PrintedFelt>>,
+    /// The results of the tests of the exercise.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub test_summary: Option<TestSummaryReport>,
+    /// The hints targeted at the error the exercise failed with, if any matched.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub hints: Option<Vec<String>>,
+}
+
+/// The values `main` returned or panicked with, as decimal felts.
+#[derive(Serialize, Debug)]
+#[serde(rename_all = "lowercase")]
+pub enum RunReport {
+    Returned(Vec<String>),
+    Panicked(Vec<String>),
+}
+
+impl From<&RunResultValue> for RunReport {
+    fn from(value: &RunResultValue) -> Self {
+        match value {
+            RunResultValue::Success(values) => RunReport::Returned(felts_to_strings(values)),
+            RunResultValue::Panic(values) => RunReport::Panicked(felts_to_strings(values)),
+        }
+    }
+}
+
+/// The results of the tests of an exercise.
+#[derive(Serialize, Debug)]
+pub struct TestSummaryReport {
+    pub passed: Vec<String>,
+    pub failed: Vec<TestFailureReport>,
+    pub ignored: Vec<String>,
+    pub filtered_out: usize,
+}
+
+/// A failed test and the data it panicked with, if it did.
+#[derive(Serialize, Debug)]
+pub struct TestFailureReport {
+    pub name: String,
+    pub panic_data: Option<Vec<String>>,
+    /// The panic data the test declared in `#[should_panic(expected = ...)]`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub expected_panic_data: Option<Vec<String>>,
+    /// Where the test is defined, formatted as a
+    /// [`SourceLocation`](starklings::diagnostics::SourceLocation).
+    pub location: String,
+    /// The felts the test printed with `debug::print`, if they could be captured.
+    pub printed: Option<Vec<PrintedFelt>>,
+}
+
+impl From<&TestsSummary> for TestSummaryReport {
+    fn from(summary: &TestsSummary) -> Self {
+        TestSummaryReport {
+            passed: summary.passed.clone(),
+            failed: summary
+                .failed
+                .iter()
+                .map(|failure| TestFailureReport {
+                    name: failure.name.clone(),
+                    panic_data: match &failure.run_result {
+                        RunResultValue::Success(_) => None,
+                        RunResultValue::Panic(values) => Some(felts_to_strings(values)),
+                    },
+                    expected_panic_data: failure
+                        .expected_panic
+                        .as_ref()
+                        .map(|values| values.iter().map(|value| value.to_string()).collect()),
+                    location: failure.location.to_string(),
+                    printed: failure.printed.clone(),
+                })
+                .collect(),
+            ignored: summary.ignored.clone(),
+            filtered_out: summary.filtered_out,
+        }
+    }
+}
+
+impl<'a> ExerciseReport<'a> {
+    /// Describes an exercise without running it.
+    pub fn new(exercise: &'a Exercise, state: State) -> Self {
+        ExerciseReport {
+            name: &exercise.name,
+            path: exercise.path.display().to_string(),
+            mode: exercise.mode,
+            state,
+            success: None,
+            output: None,
+            error: None,
+            diagnostics: None,
+            run_result: None,
+            gas_used: None,
+            printed: None,
+            test_summary: None,
+            hints: None,
+        }
+    }
+
+    /// Runs or tests the exercise according to its mode and reports the result.
+    pub fn run(exercise: &'a Exercise) -> Self {
+        let mut report = ExerciseReport::new(exercise, exercise.state());
+        let result = match exercise.mode {
+            Mode::Compile | Mode::Output if !exercise.cases.is_empty() => exercise.run_cases(),
+            Mode::Compile | Mode::Output => match exercise.run_result() {
+                Ok(run) => {
+                    report.run_result = Some(RunReport::from(&run.value));
+                    report.gas_used = run.gas_used;
+                    report.printed = Some(run.printed);
+                    Ok(describe_run_result(&run.value))
+                }
+                Err(error) => {
+                    if let Some(panicked) = error.downcast_ref::<ProgramPanicked>() {
+                        report.run_result =
+                            Some(RunReport::Panicked(felts_to_strings(&panicked.values)));
+                        report.printed = Some(panicked.printed.clone());
+                    }
+                    if let Some(exceeded) = error.downcast_ref::<GasBudgetExceeded>() {
+                        report.gas_used = Some(exceeded.gas_used);
+                    }
+                    Err(error)
+                }
+            },
+            Mode::Test | Mode::Starknet => exercise.test_results().and_then(|summary| {
+                report.test_summary = Some(TestSummaryReport::from(&summary));
+                report.gas_used = Some(summary.gas_used);
+                exercise.check_tests(summary)
+            }),
+        };
+        match result {
+            Ok(output) => {
+                report.success = Some(true);
+                report.output = Some(output);
+            }
+            Err(error) => {
+                report.success = Some(false);
+                if let Some(failed) = error.downcast_ref::<CompilationFailed>() {
+                    report.diagnostics = Some(failed.diagnostics.clone());
+                }
+                let hints = exercise.diagnostic_hints_for(&error);
+                if !hints.is_empty() {
+                    report.hints = Some(hints.into_iter().map(String::from).collect());
+                }
+                report.error = Some(error.to_string());
+            }
+        }
+        report
+    }
+
+    /// Whether the exercise was run and passed.
+    pub fn succeeded(&self) -> bool {
+        self.success == Some(true)
+    }
+}
+
+/// Prints a value as a single line of JSON.
+pub fn print_json(value: &impl Serialize) {
+    println!(
+        "{}",
+        serde_json::to_string(value).expect("Failed to serialize to JSON")
+    );
+}
+
+fn felts_to_strings(values: &[Felt]) -> Vec<String> {
+    values.iter().map(|value| value.to_string()).collect()
+}
@@ -9,22 +9,37 @@ use indicatif::ProgressBar;
 // the output from the test harnesses (if the mode of the exercise is test)
 pub fn run(exercise: &Exercise) -> Result<(), ()> {
     match exercise.mode {
-        Mode::Compile => run_cairo(exercise)?,
-        Mode::Test => test_cairo(exercise)?,
+        Mode::Compile | Mode::Output => run_cairo(exercise)?,
+        Mode::Test | Mode::Starknet => test_cairo(exercise)?,
     }
     Ok(())
 }
 
 // Resets the exercise by stashing the changes.
 pub fn reset(exercise: &Exercise) -> Result<(), ()> {
-    let command = Command::new("git")
+    let status = Command::new("git")
         .args(["stash", "--"])
         .arg(&exercise.path)
-        .spawn();
+        .status();
 
-    match command {
-        Ok(_) => Ok(()),
-        Err(_) => Err(()),
+    match status {
+        Ok(status) if status.success() => Ok(()),
+        _ => Err(()),
+    }
+}
+
+// Shows the changes made to the exercise since it was last committed,
+// the pristine version `reset` goes back to.
+// The pager is disabled, it would compete with the watch shell for the keystrokes.
+pub fn diff(exercise: &Exercise) -> Result<(), ()> {
+    let status = Command::new("git")
+        .args(["--no-pager", "diff", "HEAD", "--"])
+        .arg(&exercise.path)
+        .status();
+
+    match status {
+        Ok(status) if status.success() => Ok(()),
+        _ => Err(()),
     }
 }
 
@@ -1,19 +1,26 @@
 //! Compiles and runs a Cairo program.
 
-use std::path::Path;
+use std::collections::HashMap;
+use std::fmt::{self, Display, Formatter};
+use std::path::{Path, PathBuf};
+use std::slice;
 
-use anyhow::{Context, Ok};
+use anyhow::{bail, Context, Ok};
+use cairo_felt::Felt;
 use cairo_lang_compiler::db::RootDatabase;
-use cairo_lang_compiler::diagnostics::DiagnosticsReporter;
-use cairo_lang_compiler::project::setup_project;
 use cairo_lang_diagnostics::ToOption;
-use cairo_lang_filesystem::db::init_dev_corelib;
-use cairo_lang_runner::SierraCasmRunner;
+use cairo_lang_runner::{RunResult, RunResultValue, SierraCasmRunner};
+use cairo_lang_sierra::program::Program;
 use cairo_lang_sierra_generator::db::SierraGenGroup;
 use cairo_lang_sierra_generator::replace_ids::replace_sierra_ids_in_program;
 use clap::Parser;
-
-const CORELIB_DIR_NAME: &str = "corelib/src";
+use num_bigint::BigInt;
+use starklings::capture::{capture_stdout, parse_printed, PrintedFelt};
+use starklings::corelib::init_corelib;
+use starklings::database::{setup_exercise, with_database, DatabaseKind};
+use starklings::diagnostics::{check_diagnostics, CompilationFailed};
+use starklings::felt::{format_felts, parse_felt};
+use starklings::gas::gas_used;
 
 /// Command line args parser.
 /// Exits with 0/1 if the input is formatted correctly/incorrectly.
@@ -29,55 +36,208 @@ pub struct Args {
     /// Whether to print the memory.
     #[arg(long, default_value_t = false)]
     pub print_full_memory: bool,
+    /// The path to the corelib, otherwise looked up as `corelib::init_corelib` describes.
+    #[arg(long)]
+    pub corelib: Option<PathBuf>,
+    /// The felt252 arguments passed to `main`.
+    #[arg(last = true)]
+    pub args: Vec<String>,
 }
 
 fn main() -> anyhow::Result<()> {
     let args = Args::parse();
-    let res = run_cairo_program(&args);
-    if let Err(e) = res {
-        eprintln!("{e}");
-        std::process::exit(1);
+    match run_cairo_program(&args) {
+        Ok(result) => println!("{result}"),
+        Err(e) => {
+            match e.downcast_ref::<CompilationFailed>() {
+                Some(failed) => eprintln!("{}", failed.render()),
+                None => eprintln!("{e}"),
+            }
+            std::process::exit(1);
+        }
     }
     Ok(())
 }
 
+/// The error returned when the `main` function of a program panicked.
+#[derive(Debug)]
+pub struct ProgramPanicked {
+    /// The panic data `main` panicked with.
+    pub values: Vec<Felt>,
+    /// The felts `main` printed before panicking.
+    pub printed: Vec<PrintedFelt>,
+}
+
+impl Display for ProgramPanicked {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        if !self.printed.is_empty() {
+            writeln!(f, "{}", describe_printed(&self.printed))?;
+        }
+        write!(
+            f,
+            "Run panicked with err values: {}",
+            format_felts(&self.values)
+        )
+    }
+}
+
+impl std::error::Error for ProgramPanicked {}
+
+/// The result of running `main`, with the felts it printed.
+pub struct ProgramRun {
+    pub result: RunResult,
+    /// The felts printed with `debug::print`, empty when they could not be captured.
+    pub printed: Vec<PrintedFelt>,
+}
+
 pub fn run_cairo_program(args: &Args) -> anyhow::Result<String> {
-    let mut db = RootDatabase::default();
-    let mut corelib_dir = std::env::current_exe()
-        .unwrap_or_else(|e| panic!("Problem getting the executable path: {e:?}"));
-    corelib_dir.pop();
-    corelib_dir.pop();
-    corelib_dir.pop();
-    corelib_dir.push(CORELIB_DIR_NAME);
-    init_dev_corelib(&mut db, corelib_dir);
-
-    let main_crate_ids = setup_project(&mut db, Path::new(&args.path))?;
-
-    if DiagnosticsReporter::stderr().check(&mut db) {
-        anyhow::bail!("failed to compile: {}", args.path);
+    let ProgramRun { result, printed } = execute_cairo_program(args)?;
+    let gas_used = gas_used(args.available_gas, &result);
+
+    match result.value {
+        RunResultValue::Success(values) => {
+            let mut output = String::new();
+            if !printed.is_empty() {
+                output.push_str(&format!("{}\n", describe_printed(&printed)));
+            }
+            output.push_str(&format!(
+                "Ejecución completada con éxito, devolviendo {}",
+                format_felts(&values)
+            ));
+            if let Some(gas_used) = gas_used {
+                output.push_str(&format!("\nGas consumido: {gas_used}"));
+            }
+            Ok(output)
+        }
+        RunResultValue::Panic(values) => Err(ProgramPanicked { values, printed }.into()),
     }
+}
 
-    let mut ret_string = String::new();
+/// Describes the felts a program printed, one per line.
+pub fn describe_printed(printed: &[PrintedFelt]) -> String {
+    let mut description = "Salida del programa:".to_string();
+    for felt in printed {
+        description.push_str(&format!("\n  {felt}"));
+    }
+    description
+}
+
+/// Compiles the program and runs its `main` function, returning the raw run result
+/// and what it printed.
+pub fn execute_cairo_program(args: &Args) -> anyhow::Result<ProgramRun> {
+    execute_cairo_cases(args, slice::from_ref(&args.args))?
+        .pop()
+        .expect("A run for the single case.")
+}
+
+/// Compiles the program once and runs its `main` function with each of the given
+/// lists of arguments, returning the result of every run in the same order.
+pub fn execute_cairo_cases(
+    args: &Args,
+    cases: &[Vec<String>],
+) -> anyhow::Result<Vec<anyhow::Result<ProgramRun>>> {
+    with_database(
+        DatabaseKind::Runner,
+        || build_database(args.corelib.as_deref()),
+        |db| compile_and_run(db, args, cases),
+    )
+}
+
+fn compile_and_run(
+    db: &mut RootDatabase,
+    args: &Args,
+    cases: &[Vec<String>],
+) -> anyhow::Result<Vec<anyhow::Result<ProgramRun>>> {
+    let main_crate_ids = setup_exercise(db, Path::new(&args.path))?;
+
+    check_diagnostics(db, &main_crate_ids, &args.path)?;
 
     let sierra_program = db
         .get_sierra_program(main_crate_ids)
         .to_option()
         .with_context(|| "Compilation failed without any diagnostics.")?;
-    let runner = SierraCasmRunner::new(
-        replace_sierra_ids_in_program(&mut db, &sierra_program),
-        args.available_gas.is_some(),
-    )
-    .with_context(|| "Failed setting up runner.")?;
-    let result = runner
-        .run_function("::main", &[], args.available_gas)
-        .with_context(|| "Failed to run the function.")?;
-    match result.value {
-        cairo_lang_runner::RunResultValue::Success(values) => ret_string
-            .push_str(format!("Ejecución completada con éxito, devolviendo {values:?}").as_str()),
-        cairo_lang_runner::RunResultValue::Panic(values) => {
-            ret_string.push_str(format!("Run panicked with err values: {values:?}").as_str());
+    let sierra_program = replace_sierra_ids_in_program(db, &sierra_program);
+    let cases_args = cases
+        .iter()
+        .map(|case| main_args(&sierra_program, case))
+        .collect::<Vec<_>>();
+    let runner = SierraCasmRunner::new(sierra_program, args.available_gas.is_some())
+        .with_context(|| "Failed setting up runner.")?;
+    Ok(cases_args
+        .into_iter()
+        .map(|main_args| {
+            let main_args = main_args?;
+            let (result, printed) =
+                capture_stdout(|| runner.run_function("::main", &main_args, args.available_gas));
+            Ok(ProgramRun {
+                result: result.with_context(|| "Failed to run the function.")?,
+                printed: printed.as_deref().map(parse_printed).unwrap_or_default(),
+            })
+        })
+        .collect())
+}
+
+/// The generic types of the implicit parameters of a function, passed by the runner itself.
+const IMPLICIT_TYPES: [&str; 6] = [
+    "RangeCheck",
+    "GasBuiltin",
+    "Pedersen",
+    "Bitwise",
+    "EcOp",
+    "System",
+];
+
+/// Parses the arguments of `main`, checking them against its Sierra signature.
+/// The runner of this compiler version passes every argument as a single felt and
+/// cannot allocate arrays, so only felt252 parameters are supported, not `Array<felt252>`.
+fn main_args(sierra_program: &Program, args: &[String]) -> anyhow::Result<Vec<BigInt>> {
+    let main = sierra_program
+        .funcs
+        .iter()
+        .find(|func| {
+            func.id
+                .debug_name
+                .as_ref()
+                .map_or(false, |name| name.ends_with("::main"))
+        })
+        .with_context(|| "Function `main` not found.")?;
+    let types: HashMap<_, _> = sierra_program
+        .type_declarations
+        .iter()
+        .map(|declaration| (&declaration.id, &declaration.long_id.generic_id))
+        .collect();
+    let mut params = 0;
+    for param_type in &main.signature.param_types {
+        let generic_id = types
+            .get(param_type)
+            .with_context(|| format!("Type `{param_type}` not found."))?;
+        match generic_id.0.as_str() {
+            "felt252" => params += 1,
+            generic_id if IMPLICIT_TYPES.contains(&generic_id) => {}
+            _ => bail!(
+                "`main` takes an argument of type `{param_type}`, \
+                only felt252 arguments are supported, arrays are not."
+            ),
         }
     }
-    println!("{ret_string}");
-    Ok(ret_string)
+    if args.len() != params {
+        bail!(
+            "`main` expects {params} arguments, but {} were given.",
+            args.len()
+        );
+    }
+    args.iter()
+        .map(|arg| {
+            parse_felt(arg).map(BigInt::from).with_context(|| {
+                format!("Invalid argument `{arg}`, expected a felt or a short string.")
+            })
+        })
+        .collect()
+}
+
+/// Builds a database with the default plugins and the corelib.
+fn build_database(corelib: Option<&Path>) -> anyhow::Result<RootDatabase> {
+    let mut db = RootDatabase::default();
+    init_corelib(&mut db, corelib)?;
+    Ok(db)
 }
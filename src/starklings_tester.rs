@@ -1,17 +1,17 @@
 //! Compiles and runs a Cairo program.
 
 use std::collections::HashSet;
-use std::path::Path;
-use std::sync::{Arc, Mutex};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
 
 use anyhow::{bail, Context};
+use cairo_felt::Felt;
 use cairo_lang_compiler::db::RootDatabase;
-use cairo_lang_compiler::diagnostics::DiagnosticsReporter;
-use cairo_lang_compiler::project::setup_project;
 use cairo_lang_debug::DebugWithDb;
-use cairo_lang_defs::ids::{FreeFunctionId, FunctionWithBodyId, ModuleItemId};
+use cairo_lang_defs::db::DefsGroup;
+use cairo_lang_defs::diagnostic_utils::StableLocation;
+use cairo_lang_defs::ids::{FreeFunctionId, FunctionWithBodyId, LanguageElementId, ModuleItemId};
 use cairo_lang_diagnostics::ToOption;
-use cairo_lang_filesystem::db::init_dev_corelib;
 use cairo_lang_filesystem::ids::CrateId;
 use cairo_lang_plugins::config::ConfigPlugin;
 use cairo_lang_plugins::derive::DerivePlugin;
@@ -19,20 +19,31 @@ use cairo_lang_plugins::panicable::PanicablePlugin;
 use cairo_lang_runner::short_string::as_cairo_short_string;
 use cairo_lang_runner::{RunResultValue, SierraCasmRunner};
 use cairo_lang_semantic::db::SemanticGroup;
+use cairo_lang_semantic::items::attribute::Attribute;
 use cairo_lang_semantic::items::functions::GenericFunctionId;
 use cairo_lang_semantic::plugin::SemanticPlugin;
 use cairo_lang_semantic::{ConcreteFunction, ConcreteFunctionWithBodyId, FunctionLongId};
+use cairo_lang_sierra::program::Program;
 use cairo_lang_sierra_generator::db::SierraGenGroup;
 use cairo_lang_sierra_generator::replace_ids::replace_sierra_ids_in_program;
 use cairo_lang_starknet::plugin::StarkNetPlugin;
-use cairo_lang_syntax::node::ast::Expr;
-use cairo_lang_syntax::node::Token;
-use clap::Parser;
+use cairo_lang_syntax::node::ast::{BinaryOperator, Expr};
+use cairo_lang_syntax::node::db::SyntaxGroup;
+use cairo_lang_syntax::node::{Token, TypedSyntaxNode};
+use clap::{Parser, ValueEnum};
 use colored::Colorize;
 use itertools::Itertools;
+use num_bigint::BigUint;
 use rayon::prelude::{IntoParallelIterator, ParallelIterator};
-
-const CORELIB_DIR_NAME: &str = "corelib/src";
+use rayon::ThreadPoolBuilder;
+use starklings::capture::{capture_stdout, parse_printed, PrintedFelt};
+use starklings::corelib::init_corelib;
+use starklings::database::{setup_exercise, with_database, DatabaseKind};
+use starklings::diagnostics::{
+    check_diagnostics, diagnostic_at, CompilationFailed, Diagnostic, SourceLocation,
+};
+use starklings::felt::{format_felt, parse_felt};
+use starklings::gas::{gas_used, GasSnapshot};
 
 /// Command line args parser.
 /// Exits with 0/1 if the input is formatted correctly/incorrectly.
@@ -54,52 +65,151 @@ pub struct Args {
     /// Should we add the starknet plugin to run the tests.
     #[arg(long, default_value_t = false)]
     pub starknet: bool,
+    /// The path to the corelib, otherwise looked up as `corelib::init_corelib` describes.
+    #[arg(long)]
+    pub corelib: Option<PathBuf>,
+    /// Print a table of the gas used by each test, sorted by the given key.
+    /// VM steps are not reported, the runner of this compiler version does not expose them.
+    #[arg(long, value_enum)]
+    pub sort_by: Option<SortBy>,
+    /// Write the gas used by each test to the snapshot file.
+    #[arg(long, default_value_t = false, conflicts_with = "check_snapshot")]
+    pub snapshot: bool,
+    /// Fail if the gas used by a test differs from the snapshot file beyond the tolerance.
+    #[arg(long, default_value_t = false)]
+    pub check_snapshot: bool,
+    /// The gas snapshot file.
+    #[arg(long, default_value = ".gas-snapshot")]
+    pub snapshot_file: PathBuf,
+    /// The allowed gas difference with the snapshot, as a percentage.
+    #[arg(long, default_value_t = 0.0)]
+    pub tolerance: f64,
+    /// The number of threads running the tests, one per CPU by default.
+    #[arg(short, long, alias = "test-threads")]
+    pub jobs: Option<usize>,
+}
+
+/// The order of the rows of the gas table.
+#[derive(ValueEnum, Copy, Clone, Debug, PartialEq, Eq)]
+pub enum SortBy {
+    /// The tests using the most gas first.
+    Gas,
+    /// The tests in alphabetical order.
+    Name,
 }
 
 /// The status of a ran test.
 enum TestStatus {
     Success,
-    Fail(RunResultValue),
+    /// The test failed, with the panic data it was expected to panic with, if any.
+    Fail(RunResultValue, Option<Vec<BigUint>>),
     Ignore,
 }
 
+/// A ran test, with its status and the gas it consumed.
+struct TestRun {
+    name: String,
+    status: TestStatus,
+    gas_used: Option<usize>,
+    /// The gas the test was run with, to run it again if it failed.
+    available_gas: Option<usize>,
+    /// The location of its [`TestConfig`].
+    location: SourceLocation,
+    /// The felts the test printed, if anything was printed and it could be captured.
+    printed: Option<Vec<PrintedFelt>>,
+}
+
 fn main() -> anyhow::Result<()> {
     let args = Args::parse();
-    let res = test_cairo_program(&args);
-    if let Err(e) = res {
-        eprintln!("{e}");
-        std::process::exit(1);
+    match test_cairo_program(&args) {
+        Ok(result) => println!("{result}"),
+        Err(e) => {
+            match e.downcast_ref::<CompilationFailed>() {
+                Some(failed) => eprintln!("{}", failed.render()),
+                None => eprintln!("{e}"),
+            }
+            std::process::exit(1);
+        }
     }
     Ok(())
 }
 
 pub fn test_cairo_program(args: &Args) -> anyhow::Result<String> {
-    // TODO(orizi): Use `get_default_plugins` and just update the config plugin.
-    let mut plugins: Vec<Arc<dyn SemanticPlugin>> = vec![
-        Arc::new(DerivePlugin {}),
-        Arc::new(PanicablePlugin {}),
-        Arc::new(ConfigPlugin {
-            configs: HashSet::from(["test".to_string()]),
-        }),
-    ];
-    if args.starknet {
-        plugins.push(Arc::new(StarkNetPlugin {}));
+    let summary = run_cairo_tests(args)?;
+    let snapshot = GasSnapshot::from_tests(&summary.gas_by_test);
+    // The snapshot tests this run is responsible for: those of the tested file
+    // that the filters selected, even if they no longer exist.
+    let crate_prefix = format!(
+        "{}::",
+        Path::new(&args.path)
+            .file_stem()
+            .unwrap_or_default()
+            .to_string_lossy()
+    );
+    let ignored = summary.ignored.clone();
+    let covered = |name: &str| {
+        name.starts_with(&crate_prefix)
+            && name.contains(&args.filter)
+            && !ignored.iter().any(|ignored| ignored == name)
+    };
+    let table = args.sort_by.map(|sort_by| summary.gas_table(sort_by));
+    let mut result = match (summary.into_result(), table) {
+        (Ok(result), None) => result,
+        (Ok(result), Some(table)) => format!("{result}\n\n{table}"),
+        (Err(error), None) => return Err(error),
+        (Err(error), Some(table)) => bail!("{error}\n\n{table}"),
+    };
+    if args.snapshot {
+        snapshot.save(&args.snapshot_file, covered)?;
+        result.push_str(&format!(
+            "\n\ngas snapshot written to {}",
+            args.snapshot_file.display()
+        ));
     }
-    let db = &mut RootDatabase::builder().with_plugins(plugins).build()?;
-    let mut corelib_dir = std::env::current_exe()
-        .unwrap_or_else(|e| panic!("Problem getting the executable path: {e:?}"));
-    corelib_dir.pop();
-    corelib_dir.pop();
-    corelib_dir.pop();
-    corelib_dir.push(CORELIB_DIR_NAME);
-    init_dev_corelib(db, corelib_dir);
-
-    let main_crate_ids = setup_project(db, Path::new(&args.path))?;
-
-    if DiagnosticsReporter::stderr().check(db) {
-        bail!("failed to compile: {}", args.path);
+    if args.check_snapshot {
+        let expected = GasSnapshot::load(&args.snapshot_file)?;
+        if let Some(diff) = expected.diff(&snapshot, covered, args.tolerance) {
+            bail!(
+                "{result}\n\ngas snapshot check {}:\n{diff}",
+                "FAILED".bright_red()
+            );
+        }
+    }
+    Ok(result)
+}
+
+/// Compiles the program and runs its tests, returning a summary of the results.
+pub fn run_cairo_tests(args: &Args) -> anyhow::Result<TestsSummary> {
+    let kind = DatabaseKind::Tester {
+        starknet: args.starknet,
+    };
+    let (named_tests, sierra_program, filtered_out) = with_database(
+        kind,
+        || build_database(args.starknet, args.corelib.as_deref()),
+        |db| compile_tests(db, args),
+    )?;
+    let mut summary = run_tests(named_tests, sierra_program, args.jobs)?;
+    summary.filtered_out = filtered_out;
+    Ok(summary)
+}
+
+/// Compiles the tests of the program, returning the selected tests,
+/// their Sierra program and the number of tests left out by the filters.
+fn compile_tests(
+    db: &mut RootDatabase,
+    args: &Args,
+) -> anyhow::Result<(Vec<(String, TestConfig)>, Program, usize)> {
+    let main_crate_ids = setup_exercise(db, Path::new(&args.path))?;
+
+    check_diagnostics(db, &main_crate_ids, &args.path)?;
+    let (all_tests, diagnostics) = find_all_tests(db, main_crate_ids);
+    if !diagnostics.is_empty() {
+        return Err(CompilationFailed {
+            path: args.path.clone(),
+            diagnostics,
+        }
+        .into());
     }
-    let all_tests = find_all_tests(db, main_crate_ids);
     let sierra_program = db
         .get_sierra_program_for_functions(
             all_tests
@@ -137,136 +247,339 @@ pub fn test_cairo_program(args: &Args) -> anyhow::Result<String> {
         .filter(|(_, test)| !args.ignored || test.ignored)
         .collect_vec();
     let filtered_out = total_tests_count - named_tests.len();
-    let TestsSummary {
-        passed,
-        failed,
-        ignored,
-        failed_run_results,
-    } = run_tests(named_tests, sierra_program)?;
-    let mut result_string = String::new();
-    if failed.is_empty() {
-        result_string.push_str(
-            format!(
-                "test result: {}. {} passed; {} failed; {} ignored; {filtered_out} filtered out;",
-                "ok".bright_green(),
-                passed.len(),
-                failed.len(),
-                ignored.len()
-            )
-            .as_str(),
+    Ok((named_tests, sierra_program, filtered_out))
+}
+
+/// Builds a database with the test plugins and the corelib.
+fn build_database(starknet: bool, corelib: Option<&Path>) -> anyhow::Result<RootDatabase> {
+    // TODO(orizi): Use `get_default_plugins` and just update the config plugin.
+    let mut plugins: Vec<Arc<dyn SemanticPlugin>> = vec![
+        Arc::new(DerivePlugin {}),
+        Arc::new(PanicablePlugin {}),
+        Arc::new(ConfigPlugin {
+            configs: HashSet::from(["test".to_string()]),
+        }),
+    ];
+    if starknet {
+        plugins.push(Arc::new(StarkNetPlugin {}));
+    }
+    let mut db = RootDatabase::builder().with_plugins(plugins).build()?;
+    init_corelib(&mut db, corelib)?;
+    Ok(db)
+}
+
+/// A failed test and how it failed.
+pub struct TestFailure {
+    pub name: String,
+    pub run_result: RunResultValue,
+    /// The panic data the test was expected to panic with, if it declared any.
+    pub expected_panic: Option<Vec<BigUint>>,
+    pub location: SourceLocation,
+    /// The felts the test printed, if they could be captured.
+    pub printed: Option<Vec<PrintedFelt>>,
+}
+
+/// Summary data of the ran tests.
+pub struct TestsSummary {
+    pub passed: Vec<String>,
+    pub failed: Vec<TestFailure>,
+    pub ignored: Vec<String>,
+    /// The number of tests left out by the filters.
+    pub filtered_out: usize,
+    /// The status line of every test, in source order, followed by what
+    /// the test printed if it passed.
+    pub lines: Vec<String>,
+    /// The gas consumed by all the tests that were run with gas.
+    pub gas_used: usize,
+    /// The gas consumed by every test that was run, if it was run with gas.
+    pub gas_by_test: Vec<(String, Option<usize>)>,
+}
+
+impl TestsSummary {
+    /// Formats the gas used by every test that was run as a table.
+    pub fn gas_table(&self, sort_by: SortBy) -> String {
+        let mut rows = self.gas_by_test.clone();
+        match sort_by {
+            SortBy::Gas => rows.sort_by(|(a_name, a_gas), (b_name, b_gas)| {
+                b_gas.cmp(a_gas).then_with(|| a_name.cmp(b_name))
+            }),
+            SortBy::Name => rows.sort(),
+        }
+        let width = rows
+            .iter()
+            .map(|(name, _)| name.len())
+            .chain(["test".len()])
+            .max()
+            .unwrap_or_default();
+        let mut table = format!("{:<width$}  {:>12}", "test", "gas");
+        for (name, gas_used) in rows {
+            let gas_used = gas_used.map_or("-".to_string(), |gas| gas.to_string());
+            table.push_str(&format!("\n{name:<width$}  {gas_used:>12}"));
+        }
+        table
+    }
+
+    /// Formats the summary as the test report shown to the user.
+    /// Fails with the report if any of the tests failed.
+    pub fn into_result(self) -> anyhow::Result<String> {
+        let TestsSummary {
+            passed,
+            failed,
+            ignored,
+            filtered_out,
+            lines,
+            gas_used,
+            gas_by_test: _,
+        } = self;
+        let mut result_string = format!(
+            "running {} tests\n",
+            passed.len() + failed.len() + ignored.len()
         );
-        Ok(result_string)
-    } else {
-        result_string.push_str("failures:".to_string().as_str());
-        for (failure, run_result) in failed.iter().zip_eq(failed_run_results) {
-            result_string.push_str(format!("   {failure} - ").as_str());
-            match run_result {
-                RunResultValue::Success(_) => {
-                    result_string
-                        .push_str("expected panic but finished successfully.".to_string().as_str());
-                }
-                RunResultValue::Panic(values) => {
-                    result_string.push_str("panicked with [".to_string().as_str());
-                    for value in &values {
-                        match as_cairo_short_string(value) {
-                            Some(as_string) => result_string
-                                .push_str(format!("{value} ('{as_string}'), ").as_str()),
-                            None => result_string.push_str(format!("{value}, ").as_str()),
+        for line in lines {
+            result_string.push_str(format!("{line}\n").as_str());
+        }
+        if failed.is_empty() {
+            result_string.push_str(
+                format!(
+                    "test result: {}. {} passed; {} failed; {} ignored; {filtered_out} filtered out; {gas_used} gas used;",
+                    "ok".bright_green(),
+                    passed.len(),
+                    failed.len(),
+                    ignored.len()
+                )
+                .as_str(),
+            );
+            Ok(result_string)
+        } else {
+            result_string.push_str("\nfailures:".to_string().as_str());
+            for TestFailure {
+                name,
+                run_result,
+                expected_panic,
+                location,
+                printed,
+            } in &failed
+            {
+                result_string.push_str(format!("\n   {name} ({location}) - ").as_str());
+                match (run_result, expected_panic) {
+                    (RunResultValue::Success(_), None) => {
+                        result_string.push_str(
+                            "expected panic but finished successfully."
+                                .to_string()
+                                .as_str(),
+                        );
+                    }
+                    (RunResultValue::Success(_), Some(expected)) => {
+                        result_string.push_str(
+                            format!(
+                                "expected panic with [{}] but finished successfully.",
+                                expected.iter().map(format_felt).join(", ")
+                            )
+                            .as_str(),
+                        );
+                    }
+                    (RunResultValue::Panic(values), expected) => {
+                        result_string.push_str("panicked with [".to_string().as_str());
+                        for value in values {
+                            match as_cairo_short_string(value) {
+                                Some(as_string) => result_string
+                                    .push_str(format!("{value} ('{as_string}'), ").as_str()),
+                                None => result_string.push_str(format!("{value}, ").as_str()),
+                            }
+                        }
+                        result_string.push_str("]".to_string().as_str());
+                        if let Some(expected) = expected {
+                            result_string.push_str(
+                                format!(
+                                    ", but expected [{}]",
+                                    expected.iter().map(format_felt).join(", ")
+                                )
+                                .as_str(),
+                            );
                         }
+                        result_string.push('.');
+                        result_string.push_str(
+                            format!("\n      message: {}", panic_message(values)).as_str(),
+                        );
                     }
-                    result_string.push_str("].".to_string().as_str());
                 }
+                result_string.push_str(&printed_section(printed.as_deref(), "      "));
             }
+            result_string.push('\n');
+            bail!(
+                "{}\n\
+                test result: {}. {} passed; {} failed; {} ignored; {gas_used} gas used;",
+                result_string,
+                "FAILED".bright_red(),
+                passed.len(),
+                failed.len(),
+                ignored.len()
+            )
         }
-        println!();
-        bail!(
-            "{}\n\
-            test result: {}. {} passed; {} failed; {} ignored",
-            result_string,
-            "FAILED".bright_red(),
-            passed.len(),
-            failed.len(),
-            ignored.len()
-        )
     }
 }
 
-/// Summary data of the ran tests.
-struct TestsSummary {
-    passed: Vec<String>,
-    failed: Vec<String>,
-    ignored: Vec<String>,
-    failed_run_results: Vec<RunResultValue>,
-}
-
 /// Runs the tests and process the results for a summary.
 fn run_tests(
     named_tests: Vec<(String, TestConfig)>,
     sierra_program: cairo_lang_sierra::program::Program,
+    jobs: Option<usize>,
 ) -> anyhow::Result<TestsSummary> {
     let runner =
         SierraCasmRunner::new(sierra_program, true).with_context(|| "Failed setting up runner.")?;
-    println!("running {} tests", named_tests.len());
-    let wrapped_summary = Mutex::new(Ok(TestsSummary {
+    let run_all = || {
+        named_tests
+            .into_par_iter()
+            .map(|(name, test)| run_test(&runner, name, test))
+            .collect::<anyhow::Result<Vec<_>>>()
+    };
+    // The tests run in parallel, but their results are collected in source order.
+    // What they print is interleaved, so it is captured here and, if anything was
+    // printed, the tests are run again one at a time to tell what each one printed.
+    let (test_runs, printed) = capture_stdout(|| match jobs {
+        Some(jobs) => ThreadPoolBuilder::new()
+            .num_threads(jobs)
+            .build()
+            .with_context(|| "Failed to start the test threads.")?
+            .install(run_all),
+        None => run_all(),
+    });
+    let mut test_runs = test_runs?;
+    if printed.map_or(false, |printed| !printed.is_empty()) {
+        for test_run in &mut test_runs {
+            if !matches!(test_run.status, TestStatus::Ignore) {
+                let (_, printed) = capture_stdout(|| {
+                    runner.run_function(test_run.name.as_str(), &[], test_run.available_gas)
+                });
+                test_run.printed = printed.as_deref().map(parse_printed);
+            }
+        }
+    }
+    let mut summary = TestsSummary {
         passed: vec![],
         failed: vec![],
         ignored: vec![],
-        failed_run_results: vec![],
-    }));
-    named_tests
-        .into_par_iter()
-        .map(|(name, test)| -> anyhow::Result<(String, TestStatus)> {
-            if test.ignored {
-                return Ok((name, TestStatus::Ignore));
-            }
-            let result = runner
-                .run_function(name.as_str(), &[], test.available_gas)
-                .with_context(|| "Failed to run the function.")?;
-            Ok((
-                name,
-                match (&result.value, test.expectation) {
-                    (RunResultValue::Success(_), TestExpectation::Success)
-                    | (RunResultValue::Panic(_), TestExpectation::Panics) => TestStatus::Success,
-                    (RunResultValue::Success(_), TestExpectation::Panics)
-                    | (RunResultValue::Panic(_), TestExpectation::Success) => {
-                        TestStatus::Fail(result.value)
-                    }
-                },
-            ))
-        })
-        .for_each(|r| {
-            let mut wrapped_summary = wrapped_summary.lock().unwrap();
-            if wrapped_summary.is_err() {
-                return;
+        filtered_out: 0,
+        lines: vec![],
+        gas_used: 0,
+        gas_by_test: vec![],
+    };
+    for TestRun {
+        name,
+        status,
+        gas_used,
+        available_gas: _,
+        location,
+        printed,
+    } in test_runs
+    {
+        summary.gas_used += gas_used.unwrap_or_default();
+        let ran = !matches!(status, TestStatus::Ignore);
+        let status_str = match status {
+            TestStatus::Success => "ok".bright_green(),
+            TestStatus::Fail(..) => "fail".bright_red(),
+            TestStatus::Ignore => "ignored".bright_yellow(),
+        };
+        let mut line = match gas_used {
+            Some(gas_used) => format!("test {name} ... {status_str} (gas: {gas_used})"),
+            None => format!("test {name} ... {status_str}"),
+        };
+        match status {
+            TestStatus::Success => {
+                line.push_str(&printed_section(printed.as_deref(), "    "));
+                summary.passed.push(name.clone());
             }
-            let (name, status) = match r {
-                Ok((name, status)) => (name, status),
-                Err(err) => {
-                    *wrapped_summary = Err(err);
-                    return;
-                }
-            };
-            let summary = wrapped_summary.as_mut().unwrap();
-            let (res_type, status_str) = match status {
-                TestStatus::Success => (&mut summary.passed, "ok".bright_green()),
-                TestStatus::Fail(run_result) => {
-                    summary.failed_run_results.push(run_result);
-                    (&mut summary.failed, "fail".bright_red())
-                }
-                TestStatus::Ignore => (&mut summary.ignored, "ignored".bright_yellow()),
-            };
-            println!("test {name} ... {status_str}",);
-            res_type.push(name);
+            TestStatus::Fail(run_result, expected_panic) => summary.failed.push(TestFailure {
+                name: name.clone(),
+                run_result,
+                expected_panic,
+                location,
+                printed,
+            }),
+            TestStatus::Ignore => summary.ignored.push(name.clone()),
+        }
+        summary.lines.push(line);
+        if ran {
+            summary.gas_by_test.push((name, gas_used));
+        }
+    }
+    Ok(summary)
+}
+
+/// Formats the felts a test printed as a section indented by `indent`,
+/// empty when the test printed nothing.
+fn printed_section(printed: Option<&[PrintedFelt]>, indent: &str) -> String {
+    let mut section = String::new();
+    if let Some(printed) = printed.filter(|printed| !printed.is_empty()) {
+        section.push_str(&format!("\n{indent}printed:"));
+        for felt in printed {
+            section.push_str(&format!("\n{indent}  {felt}"));
+        }
+    }
+    section
+}
+
+/// Runs a single test, comparing its result with the expected one.
+fn run_test(runner: &SierraCasmRunner, name: String, test: TestConfig) -> anyhow::Result<TestRun> {
+    if test.ignored {
+        return Ok(TestRun {
+            name,
+            status: TestStatus::Ignore,
+            gas_used: None,
+            available_gas: test.available_gas,
+            location: test.location,
+            printed: None,
         });
-    wrapped_summary.into_inner().unwrap()
+    }
+    let result = runner
+        .run_function(name.as_str(), &[], test.available_gas)
+        .with_context(|| "Failed to run the function.")?;
+    let gas_used = gas_used(test.available_gas, &result);
+    Ok(TestRun {
+        name,
+        status: match (&result.value, test.expectation) {
+            (RunResultValue::Success(_), TestExpectation::Success) => TestStatus::Success,
+            (RunResultValue::Panic(values), TestExpectation::Panics(expected))
+                if panic_data_matches(values, expected.as_deref()) =>
+            {
+                TestStatus::Success
+            }
+            (_, TestExpectation::Success) => TestStatus::Fail(result.value, None),
+            (_, TestExpectation::Panics(expected)) => TestStatus::Fail(result.value, expected),
+        },
+        gas_used,
+        available_gas: test.available_gas,
+        location: test.location,
+        printed: None,
+    })
+}
+
+/// Decodes panic data as text, showing the felts that are not short strings as numbers.
+fn panic_message(values: &[Felt]) -> String {
+    values
+        .iter()
+        .map(|value| as_cairo_short_string(value).unwrap_or_else(|| value.to_string()))
+        .join(" ")
 }
 
 /// Expectation for a result of a test.
 enum TestExpectation {
     /// Running the test should not panic.
     Success,
-    /// Running the test should result in a panic.
-    Panics,
+    /// Running the test should result in a panic, with the given panic data if any.
+    Panics(Option<Vec<BigUint>>),
+}
+
+/// Whether a test panicked with the data it declared, if it declared any.
+fn panic_data_matches(values: &[Felt], expected: Option<&[BigUint]>) -> bool {
+    let Some(expected) = expected else {
+        return true;
+    };
+    values.len() == expected.len()
+        && values
+            .iter()
+            .zip(expected)
+            .all(|(value, expected)| value.to_biguint() == *expected)
 }
 
 /// The configuration for running a single test.
@@ -279,11 +592,21 @@ struct TestConfig {
     expectation: TestExpectation,
     /// Should the test be ignored.
     ignored: bool,
+    /// Where the test is defined.
+    location: SourceLocation,
 }
 
+/// The attributes a test function may carry.
+const TEST_ATTRIBUTES: [&str; 5] = ["test", "ignore", "available_gas", "should_panic", "inline"];
+
 /// Finds the tests in the requested crates.
-fn find_all_tests(db: &dyn SemanticGroup, main_crates: Vec<CrateId>) -> Vec<TestConfig> {
+/// Malformed or unknown attributes on tests are reported as diagnostics.
+fn find_all_tests(
+    db: &dyn SemanticGroup,
+    main_crates: Vec<CrateId>,
+) -> (Vec<TestConfig>, Vec<Diagnostic>) {
     let mut tests = vec![];
+    let mut diagnostics = vec![];
     for crate_id in main_crates {
         let modules = db.crate_modules(crate_id);
         for module_id in modules.iter() {
@@ -299,41 +622,47 @@ fn find_all_tests(db: &dyn SemanticGroup, main_crates: Vec<CrateId>) -> Vec<Test
                         let mut is_test = false;
                         let mut available_gas = None;
                         let mut ignored = false;
-                        let mut should_panic = false;
-                        for attr in attrs {
+                        let mut should_panic = None;
+                        let mut errors = vec![];
+                        for attr in attrs.iter() {
                             match attr.id.as_str() {
                                 "test" => {
                                     is_test = true;
                                 }
-                                "available_gas" => {
-                                    // TODO(orizi): Provide diagnostics when this does not match.
-                                    if let [Expr::Literal(literal)] = &attr.args[..] {
-                                        available_gas = literal
-                                            .token(db.upcast())
-                                            .text(db.upcast())
-                                            .parse::<usize>()
-                                            .ok();
-                                    }
-                                }
-                                "should_panic" => {
-                                    should_panic = true;
-                                }
+                                "available_gas" => match parse_available_gas(db, &attr.args) {
+                                    Some(gas) => available_gas = Some(gas),
+                                    None => errors.push((
+                                        attr,
+                                        "`#[available_gas]` expects a single integer, as in \
+                                        `#[available_gas(20000)]`."
+                                            .to_string(),
+                                    )),
+                                },
+                                "should_panic" => match expected_panic_data(db, &attr.args) {
+                                    Ok(expected) => should_panic = Some(expected),
+                                    Err(message) => errors.push((attr, message)),
+                                },
                                 "ignore" => {
                                     ignored = true;
                                 }
+                                id if !TEST_ATTRIBUTES.contains(&id) => errors
+                                    .push((attr, format!("Unknown test attribute `#[{id}]`."))),
                                 _ => {}
                             }
                         }
                         if is_test {
+                            diagnostics.extend(errors.into_iter().map(|(attr, message)| {
+                                attribute_diagnostic(db, *func_id, attr, message)
+                            }));
                             tests.push(TestConfig {
                                 func_id: *func_id,
                                 available_gas,
-                                expectation: if should_panic {
-                                    TestExpectation::Panics
-                                } else {
-                                    TestExpectation::Success
+                                expectation: match should_panic {
+                                    Some(expected) => TestExpectation::Panics(expected),
+                                    None => TestExpectation::Success,
                                 },
                                 ignored,
+                                location: function_location(db, *func_id),
                             })
                         }
                     }
@@ -341,5 +670,119 @@ fn find_all_tests(db: &dyn SemanticGroup, main_crates: Vec<CrateId>) -> Vec<Test
             }
         }
     }
-    tests
+    (tests, diagnostics)
+}
+
+/// Parses the gas of `#[available_gas(20000)]`.
+fn parse_available_gas(db: &dyn SemanticGroup, args: &[Expr]) -> Option<usize> {
+    let [Expr::Literal(literal)] = args else {
+        return None;
+    };
+    literal
+        .token(db.upcast())
+        .text(db.upcast())
+        .parse::<usize>()
+        .ok()
+}
+
+/// Builds a diagnostic pointing to an attribute of a test function.
+fn attribute_diagnostic(
+    db: &dyn SemanticGroup,
+    func_id: FreeFunctionId,
+    attr: &Attribute,
+    message: String,
+) -> Diagnostic {
+    let defs_db: &dyn DefsGroup = db.upcast();
+    let location = StableLocation::new(func_id.module_file_id(defs_db), attr.stable_ptr.untyped())
+        .diagnostic_location(defs_db);
+    diagnostic_at(db.upcast(), &location, message)
+}
+
+/// Where a test function is defined.
+fn function_location(db: &dyn SemanticGroup, func_id: FreeFunctionId) -> SourceLocation {
+    let defs_db: &dyn DefsGroup = db.upcast();
+    let location = StableLocation::new(
+        func_id.module_file_id(defs_db),
+        func_id.stable_ptr(defs_db).untyped(),
+    )
+    .diagnostic_location(defs_db);
+    SourceLocation::start_of(db.upcast(), &location)
+}
+
+/// Parses the panic data of `#[should_panic(expected = (...))]`.
+/// The data is a tuple of felts or short strings, such as `expected = ('error', 1)`.
+/// Attribute arguments are expressions in this compiler version, so the
+/// `expected: (...)` form of later versions is written as an assignment.
+/// Returns `None` for a bare `#[should_panic]`, which accepts any panic.
+fn expected_panic_data(
+    db: &dyn SemanticGroup,
+    args: &[Expr],
+) -> Result<Option<Vec<BigUint>>, String> {
+    let syntax_db: &dyn SyntaxGroup = db.upcast();
+    let usage = "`#[should_panic]` expects the panic data as `expected = ('error', 1)`.";
+    let binary = match args {
+        [] => return Ok(None),
+        [Expr::Binary(binary)] => binary,
+        _ => return Err(usage.to_string()),
+    };
+    let text = |expr: &Expr| expr.as_syntax_node().get_text(syntax_db);
+    let is_expected = matches!(binary.op(syntax_db), BinaryOperator::Eq(_))
+        && text(&binary.lhs(syntax_db)).trim() == "expected";
+    if !is_expected {
+        return Err(usage.to_string());
+    }
+    let values = match binary.rhs(syntax_db) {
+        Expr::Tuple(tuple) => tuple.expressions(syntax_db).elements(syntax_db),
+        Expr::Parenthesized(parenthesized) => vec![parenthesized.expr(syntax_db)],
+        value => vec![value],
+    };
+    values
+        .iter()
+        .map(|value| {
+            let value = text(value);
+            parse_felt(&value).ok_or_else(|| {
+                format!(
+                    "Invalid panic data `{}`, expected a felt or a short string.",
+                    value.trim()
+                )
+            })
+        })
+        .collect::<Result<_, _>>()
+        .map(Some)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn summary(gas_by_test: &[(&str, Option<usize>)]) -> TestsSummary {
+        TestsSummary {
+            passed: vec![],
+            failed: vec![],
+            ignored: vec![],
+            filtered_out: 0,
+            lines: vec![],
+            gas_used: 0,
+            gas_by_test: gas_by_test
+                .iter()
+                .map(|(name, gas_used)| (name.to_string(), *gas_used))
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn test_gas_table_sorted_by_gas() {
+        let summary = summary(&[
+            ("a::cheap", Some(10)),
+            ("a::no_gas", None),
+            ("a::costly", Some(300)),
+        ]);
+
+        let table = summary.gas_table(SortBy::Gas);
+        let rows: Vec<&str> = table
+            .lines()
+            .map(|line| line.split_whitespace().next().unwrap())
+            .collect();
+        assert_eq!(rows, ["test", "a::costly", "a::cheap", "a::no_gas"]);
+    }
 }
@@ -1,7 +1,12 @@
-use crate::exercise::{Exercise, Mode, State};
+use crate::exercise::{CasesFailed, Exercise, GasBudgetExceeded, Mode, State};
+use crate::output::OutputMismatch;
+use crate::report::{print_json, ExerciseReport};
+use crate::starklings_runner::ProgramPanicked;
 use console::style;
 use indicatif::{ProgressBar, ProgressStyle};
+use starklings::diagnostics::CompilationFailed;
 use std::env;
+use std::io;
 
 // Verify that the provided container of Exercise objects
 // can be compiled and run without any failures.
@@ -22,10 +27,16 @@ pub fn verify<'a>(
     bar.set_position(num_done as u64);
     for exercise in exercises {
         let compile_result = match exercise.mode {
-            Mode::Compile => compile_and_run_interactively(exercise),
-            Mode::Test => compile_and_test_interactively(exercise),
+            Mode::Compile | Mode::Output => compile_and_run_interactively(exercise),
+            Mode::Test | Mode::Starknet => compile_and_test_interactively(exercise),
         };
         if !compile_result.unwrap_or(false) {
+            if let Ok(true) = exercise.clear_verified() {
+                warn!(
+                    "{} estaba resuelto, pero ya no pasa la verificación.",
+                    exercise
+                );
+            }
             return Err(exercise);
         }
         let percentage = num_done as f32 / total as f32 * 100.0;
@@ -35,6 +46,37 @@ pub fn verify<'a>(
     Ok(())
 }
 
+// Verify the exercises like `verify` does, but print a JSON report per
+// exercise on its own line instead of the interactive output.
+pub fn verify_json<'a>(
+    exercises: impl IntoIterator<Item = &'a Exercise>,
+) -> Result<(), &'a Exercise> {
+    for exercise in exercises {
+        let (report, recorded) = verify_report(exercise);
+        if let Err(error) = recorded {
+            eprintln!("No se pudo guardar el progreso de {exercise}: {error}");
+        }
+        print_json(&report);
+        if !report.succeeded() {
+            return Err(exercise);
+        }
+    }
+    Ok(())
+}
+
+// Run the given Exercise without printing anything and record the result
+// in the progress, returning the report along with whether it could be saved
+pub fn verify_report(exercise: &Exercise) -> (ExerciseReport<'_>, io::Result<()>) {
+    let mut report = ExerciseReport::run(exercise);
+    let recorded = if report.succeeded() {
+        exercise.mark_verified()
+    } else {
+        exercise.clear_verified().map(|_| ())
+    };
+    report.state = exercise.state();
+    (report, recorded)
+}
+
 // Compile the given Exercise and run the resulting binary in an interactive mode
 fn compile_and_run_interactively(exercise: &Exercise) -> Result<bool, ()> {
     let progress_bar = ProgressBar::new_spinner();
@@ -73,11 +115,33 @@ fn compile_and_run_cairo<'a, 'b>(
 
     if let Some(error) = compilation_result.as_ref().err() {
         progress_bar.finish_and_clear();
-        warn!(
-            "Compilación de {} ¡Ha fallado! Por favor, inténtelo de nuevo. Aquí está el resultado:",
-            exercise
-        );
-        println!("{error}");
+        if error.downcast_ref::<ProgramPanicked>().is_some() {
+            warn!(
+                "La ejecución de {} ha hecho panic. Por favor, inténtelo de nuevo. Aquí está el resultado:",
+                exercise
+            );
+        } else if error.downcast_ref::<GasBudgetExceeded>().is_some() {
+            warn!(
+                "{} consume demasiado gas. Por favor, optimice su solución:",
+                exercise
+            );
+        } else if error.downcast_ref::<CasesFailed>().is_some() {
+            warn!(
+                "Algunos casos de {} no devuelven el resultado esperado. Por favor, inténtelo de nuevo:",
+                exercise
+            );
+        } else if error.downcast_ref::<OutputMismatch>().is_some() {
+            warn!(
+                "La salida de {} no es la esperada. Por favor, inténtelo de nuevo. Aquí está la diferencia:",
+                exercise
+            );
+        } else {
+            warn!(
+                "Compilación de {} ¡Ha fallado! Por favor, inténtelo de nuevo. Aquí está el resultado:",
+                exercise
+            );
+        }
+        print_error(exercise, error);
         Err(())
     } else {
         Ok(compilation_result.unwrap())
@@ -94,26 +158,33 @@ fn compile_and_test_cairo<'a, 'b>(
 
     if let Some(error) = compilation_result.as_ref().err() {
         progress_bar.finish_and_clear();
-        warn!(
-            "Testing de {} ¡Ha fallado! Por favor, inténtelo de nuevo. Aquí está el resultado:",
-            exercise
-        );
-        println!("{error}");
-        Err(())
-    } else {
-        Ok(compilation_result.unwrap())
+        if error.downcast_ref::<GasBudgetExceeded>().is_some() {
+            warn!(
+                "Los tests de {} consumen demasiado gas. Por favor, optimice su solución:",
+                exercise
+            );
+        } else {
+            warn!(
+                "Testing de {} ¡Ha fallado! Por favor, inténtelo de nuevo. Aquí está el resultado:",
+                exercise
+            );
+        }
+        print_error(exercise, error);
+        return Err(());
     }
+
+    Ok(compilation_result.unwrap())
 }
 
 fn prompt_for_completion(exercise: &Exercise, prompt_output: Option<String>) -> bool {
-    let context = match exercise.state() {
-        State::Done => return true,
-        State::Pending(context) => context,
-    };
+    if exercise.state() == State::Done {
+        return true;
+    }
 
     match exercise.mode {
-        Mode::Compile => success!("Ejecutado con éxito {}!", exercise),
+        Mode::Compile | Mode::Output => success!("Ejecutado con éxito {}!", exercise),
         Mode::Test => success!("Testeado con éxito {}!", exercise),
+        Mode::Starknet => success!("Contrato testeado con éxito {}!", exercise),
         // Mode::Clippy => success!("Successfully compiled {}!", exercise),
     }
 
@@ -124,6 +195,8 @@ fn prompt_for_completion(exercise: &Exercise, prompt_output: Option<String>) ->
     let success_msg = match exercise.mode {
         Mode::Compile => "¡El código se está compilando!",
         Mode::Test => "El código se está compilando, ¡y los test pasan!",
+        Mode::Starknet => "El contrato se está compilando, ¡y los test pasan!",
+        Mode::Output => "El código se está compilando, ¡y devuelve el resultado esperado!",
         // Mode::Clippy => clippy_success_msg,
     };
 
@@ -143,28 +216,23 @@ fn prompt_for_completion(exercise: &Exercise, prompt_output: Option<String>) ->
         println!();
     }
 
-    println!("Puedes seguir trabajando en este ejercicio,");
-    println!(
-        "o saltar al siguiente eliminando el comentario {}:",
-        style("`I AM NOT DONE`").bold()
-    );
-    println!();
-    for context_line in context {
-        let formatted_line = if context_line.important {
-            format!("{}", style(context_line.line).bold())
-        } else {
-            context_line.line.to_string()
-        };
-
-        println!(
-            "{:>2} {}  {}",
-            style(context_line.number).blue().bold(),
-            style("|").blue(),
-            formatted_line
-        );
+    if let Err(error) = exercise.mark_verified() {
+        println!("No se pudo guardar el progreso de {exercise}: {error}");
     }
 
-    false
+    true
+}
+
+// Print the error of a failed run, rendering the source snippets the compiler
+// diagnostics point to when it did not compile, followed by the hints targeted at it
+fn print_error(exercise: &Exercise, error: &anyhow::Error) {
+    match error.downcast_ref::<CompilationFailed>() {
+        Some(failed) => println!("{}", failed.render()),
+        None => println!("{error}"),
+    }
+    for hint in exercise.diagnostic_hints_for(error) {
+        println!("{} {hint}", style("Pista:").bold());
+    }
 }
 
 fn separator() -> console::StyledObject<&'static str> {
@@ -0,0 +1,347 @@
+//! Full-screen terminal UI of `watch --tui`.
+//! A sidebar lists the exercises grouped by topic with their state, and the
+//! main pane shows the result of verifying the selected exercise, which is
+//! verified again whenever its file is saved. The hint pane shows the levels
+//! of the hint revealed so far, and the hints targeted at the error the
+//! exercise failed with follow the error.
+
+use std::ffi::OsStr;
+use std::io::{self, Stdout};
+use std::panic::{self, PanicInfo};
+use std::path::Path;
+use std::sync::mpsc::{channel, Receiver};
+use std::sync::Arc;
+use std::time::Duration;
+
+use crossterm::cursor::Show;
+use crossterm::event::{self, Event, KeyCode, KeyEvent};
+use crossterm::execute;
+use crossterm::terminal::{
+    disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen,
+};
+use notify::{DebouncedEvent, RecommendedWatcher, RecursiveMode, Watcher};
+use tui::backend::CrosstermBackend;
+use tui::layout::{Constraint, Direction, Layout, Rect};
+use tui::style::{Color, Modifier, Style};
+use tui::text::{Span, Spans, Text};
+use tui::widgets::{Block, Borders, List, ListItem, ListState, Paragraph, Wrap};
+use tui::{Frame, Terminal};
+
+use crate::exercise::{Exercise, State};
+use crate::progress::Progress;
+use crate::verify::verify_report;
+
+type Backend = CrosstermBackend<Stdout>;
+
+/// How long to wait for a key press before checking for edited files.
+const TICK: Duration = Duration::from_millis(200);
+
+/// A row of the sidebar.
+enum Row {
+    /// The name of a topic, above its exercises.
+    Topic(String),
+    /// The exercise at the given index.
+    Exercise(usize),
+}
+
+/// The result of the last verification of the selected exercise,
+/// `None` while it is being verified.
+struct Outcome {
+    success: bool,
+    text: String,
+}
+
+struct App<'a> {
+    exercises: &'a [Exercise],
+    rows: Vec<Row>,
+    progress: Progress,
+    /// The state of every exercise, computed again after each verification
+    /// instead of hashing every file on each redraw.
+    states: Vec<State>,
+    selected: usize,
+    outcome: Option<Outcome>,
+    show_hint: bool,
+}
+
+/// Runs the watch UI until the learner quits.
+pub fn watch(exercises: &[Exercise]) -> anyhow::Result<()> {
+    let (tx, rx) = channel();
+    let mut watcher: RecommendedWatcher = Watcher::new(tx, Duration::from_secs(2))?;
+    watcher.watch(Path::new("./exercises"), RecursiveMode::Recursive)?;
+
+    // The output is shown in the panes, the escape codes of colored text would garble it.
+    console::set_colors_enabled(false);
+    colored::control::set_override(false);
+
+    enable_raw_mode()?;
+    let mut stdout = io::stdout();
+    execute!(stdout, EnterAlternateScreen)?;
+    // A panic in the compiler or the runner would otherwise leave the terminal in
+    // raw mode on the alternate screen, hiding the panic message.
+    let previous_hook: Arc<dyn Fn(&PanicInfo) + Sync + Send> = Arc::from(panic::take_hook());
+    let hook = previous_hook.clone();
+    panic::set_hook(Box::new(move |info| {
+        let _ = restore_terminal();
+        hook(info);
+    }));
+    let mut terminal = Terminal::new(CrosstermBackend::new(stdout))?;
+    // The terminal is restored even when the UI failed.
+    let result = run(&mut terminal, App::new(exercises), &rx);
+    panic::set_hook(Box::new(move |info| previous_hook(info)));
+    restore_terminal()?;
+    result
+}
+
+/// Leaves the raw mode and the alternate screen, showing the cursor again.
+fn restore_terminal() -> io::Result<()> {
+    disable_raw_mode()?;
+    execute!(io::stdout(), LeaveAlternateScreen, Show)
+}
+
+fn run(
+    terminal: &mut Terminal<Backend>,
+    mut app: App,
+    rx: &Receiver<DebouncedEvent>,
+) -> anyhow::Result<()> {
+    app.verify(terminal)?;
+    loop {
+        terminal.draw(|f| app.draw(f))?;
+        if event::poll(TICK)? {
+            if let Event::Key(KeyEvent { code, .. }) = event::read()? {
+                match code {
+                    KeyCode::Char('q') | KeyCode::Esc => return Ok(()),
+                    KeyCode::Char('n') | KeyCode::Down => app.select(app.selected + 1, terminal)?,
+                    KeyCode::Char('p') | KeyCode::Up => {
+                        app.select(app.selected.saturating_sub(1), terminal)?
+                    }
+                    KeyCode::Char('h') => app.hint(),
+                    KeyCode::Char('r') => app.verify(terminal)?,
+                    _ => {}
+                }
+            }
+        }
+        while let Ok(event) = rx.try_recv() {
+            if let DebouncedEvent::Create(path)
+            | DebouncedEvent::Chmod(path)
+            | DebouncedEvent::Write(path) = event
+            {
+                if path.extension() != Some(OsStr::new("cairo")) || !path.exists() {
+                    continue;
+                }
+                let path = path.canonicalize()?;
+                if let Some(index) = app.exercises.iter().position(|e| path.ends_with(&e.path)) {
+                    app.selected = index;
+                    app.verify(terminal)?;
+                }
+            }
+        }
+    }
+}
+
+impl<'a> App<'a> {
+    fn new(exercises: &'a [Exercise]) -> Self {
+        let progress = Progress::load();
+        let mut rows = vec![];
+        let mut last_topic = None;
+        for (index, exercise) in exercises.iter().enumerate() {
+            let topic = topic(exercise);
+            if last_topic.as_ref() != Some(&topic) {
+                rows.push(Row::Topic(topic.clone()));
+                last_topic = Some(topic);
+            }
+            rows.push(Row::Exercise(index));
+        }
+        let states = states(exercises, &progress);
+        let selected = states
+            .iter()
+            .position(|state| *state != State::Done)
+            .unwrap_or_default();
+        App {
+            exercises,
+            rows,
+            progress,
+            states,
+            selected,
+            outcome: None,
+            show_hint: false,
+        }
+    }
+
+    /// Selects the exercise at the given index, if there is one, and verifies it.
+    fn select(&mut self, index: usize, terminal: &mut Terminal<Backend>) -> anyhow::Result<()> {
+        if index >= self.exercises.len() || index == self.selected {
+            return Ok(());
+        }
+        self.selected = index;
+        self.show_hint = false;
+        self.verify(terminal)
+    }
+
+    /// Shows the hint of the selected exercise, revealing its first level.
+    /// While it is shown, reveals the next level, or hides it once every level was revealed.
+    fn hint(&mut self) {
+        let exercise = &self.exercises[self.selected];
+        let revealed = exercise.hint_level_in(&self.progress);
+        let reveal = if self.show_hint {
+            revealed < exercise.hint.levels().len()
+        } else {
+            revealed == 0
+        };
+        if self.show_hint && !reveal {
+            self.show_hint = false;
+            return;
+        }
+        self.show_hint = true;
+        if reveal {
+            let (level, recorded) = exercise.reveal_hint();
+            self.progress.set_hint_level(&exercise.path, level);
+            if let (Err(error), Some(outcome)) = (recorded, &mut self.outcome) {
+                outcome.text.push_str(&format!(
+                    "\n\nNo se pudo guardar la pista revelada de {exercise}: {error}"
+                ));
+            }
+        }
+    }
+
+    /// Verifies the selected exercise, recording the result in the progress.
+    fn verify(&mut self, terminal: &mut Terminal<Backend>) -> anyhow::Result<()> {
+        let exercise = &self.exercises[self.selected];
+        self.outcome = None;
+        terminal.draw(|f| self.draw(f))?;
+        let (report, recorded) = verify_report(exercise);
+        let mut text = match (&report.output, &report.error) {
+            (Some(output), _) => output.clone(),
+            (None, Some(error)) => error.clone(),
+            (None, None) => String::new(),
+        };
+        for hint in report.hints.iter().flatten() {
+            text.push_str(&format!("\n\nPista: {hint}"));
+        }
+        if let Err(error) = recorded {
+            text.push_str(&format!(
+                "\n\nNo se pudo guardar el progreso de {exercise}: {error}"
+            ));
+        }
+        self.outcome = Some(Outcome {
+            success: report.succeeded(),
+            text,
+        });
+        self.progress = Progress::load();
+        self.states = states(self.exercises, &self.progress);
+        Ok(())
+    }
+
+    fn draw(&self, f: &mut Frame<Backend>) {
+        let columns = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([Constraint::Length(32), Constraint::Min(0)])
+            .split(f.size());
+        self.draw_sidebar(f, columns[0]);
+
+        let hint_height = if self.show_hint { 30 } else { 0 };
+        let panes = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Min(0),
+                Constraint::Percentage(hint_height),
+                Constraint::Length(1),
+            ])
+            .split(columns[1]);
+        self.draw_outcome(f, panes[0]);
+        if self.show_hint {
+            let exercise = &self.exercises[self.selected];
+            let text = (1..=exercise.hint_level_in(&self.progress))
+                .map(|level| exercise.hint.describe_level(level))
+                .collect::<Vec<_>>()
+                .join("\n\n");
+            let hint = Paragraph::new(text)
+                .block(Block::default().borders(Borders::ALL).title("Pista"))
+                .wrap(Wrap { trim: false });
+            f.render_widget(hint, panes[1]);
+        }
+        let keys =
+            Paragraph::new("n/p: siguiente/anterior  h: pista  r: volver a verificar  q: salir")
+                .style(Style::default().add_modifier(Modifier::DIM));
+        f.render_widget(keys, panes[2]);
+    }
+
+    fn draw_sidebar(&self, f: &mut Frame<Backend>, area: Rect) {
+        let items = self
+            .rows
+            .iter()
+            .map(|row| match row {
+                Row::Topic(topic) => ListItem::new(Span::styled(
+                    topic.clone(),
+                    Style::default().add_modifier(Modifier::BOLD),
+                )),
+                Row::Exercise(index) => {
+                    let exercise = &self.exercises[*index];
+                    let (mark, color) = match self.states[*index] {
+                        State::Done => ("✓", Color::Green),
+                        State::Modified => ("~", Color::Yellow),
+                        State::Pending => ("·", Color::Gray),
+                    };
+                    ListItem::new(Spans::from(vec![
+                        Span::styled(format!("  {mark} "), Style::default().fg(color)),
+                        Span::raw(exercise.name.clone()),
+                    ]))
+                }
+            })
+            .collect::<Vec<_>>();
+        let done = self
+            .states
+            .iter()
+            .filter(|state| **state == State::Done)
+            .count();
+        let list = List::new(items)
+            .block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .title(format!("Ejercicios {done}/{}", self.exercises.len())),
+            )
+            .highlight_style(Style::default().add_modifier(Modifier::REVERSED));
+        let mut state = ListState::default();
+        state.select(
+            self.rows
+                .iter()
+                .position(|row| matches!(row, Row::Exercise(index) if *index == self.selected)),
+        );
+        f.render_stateful_widget(list, area, &mut state);
+    }
+
+    fn draw_outcome(&self, f: &mut Frame<Backend>, area: Rect) {
+        let exercise = &self.exercises[self.selected];
+        let (title, color) = match &self.outcome {
+            Some(Outcome { success: true, .. }) => (format!("{exercise} ✓"), Color::Green),
+            Some(_) => (format!("{exercise} ✗"), Color::Red),
+            None => (exercise.to_string(), Color::Reset),
+        };
+        let text = match &self.outcome {
+            Some(outcome) => outcome.text.clone(),
+            None => format!("Verificando {exercise}..."),
+        };
+        let outcome = Paragraph::new(Text::raw(text))
+            .block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .title(Span::styled(title, Style::default().fg(color))),
+            )
+            .wrap(Wrap { trim: false });
+        f.render_widget(outcome, area);
+    }
+}
+
+/// The state of every exercise in the given progress.
+fn states(exercises: &[Exercise], progress: &Progress) -> Vec<State> {
+    exercises.iter().map(|e| e.state_in(progress)).collect()
+}
+
+/// The topic of an exercise, the directory it is in.
+fn topic(exercise: &Exercise) -> String {
+    exercise
+        .path
+        .parent()
+        .and_then(Path::file_name)
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_default()
+}
@@ -0,0 +1,3 @@
+fn main(a: felt252, b: felt252) -> felt252 {
+    a + b
+}
@@ -0,0 +1,3 @@
+fn main(n: felt252) -> felt252 {
+    n * 2
+}
@@ -0,0 +1,4 @@
+fn main() -> felt252 {
+    assert(1 == 2, 'one is not two');
+    25
+}
@@ -0,0 +1,6 @@
+use debug::PrintTrait;
+
+fn main() -> felt252 {
+    'hola'.print();
+    25
+}
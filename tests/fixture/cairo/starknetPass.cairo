@@ -0,0 +1,27 @@
+#[contract]
+mod Counter {
+    struct Storage {
+        counter: felt252,
+    }
+
+    #[event]
+    fn CounterIncreased(amount: felt252) {}
+
+    #[external]
+    fn increase(amount: felt252) {
+        counter::write(counter::read() + amount);
+        CounterIncreased(amount);
+    }
+
+    #[view]
+    fn get_counter() -> felt252 {
+        counter::read()
+    }
+}
+
+#[test]
+#[available_gas(2000000)]
+fn test_increase() {
+    Counter::increase(5);
+    assert(Counter::get_counter() == 5, 'counter should be 5');
+}
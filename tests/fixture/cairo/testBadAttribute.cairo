@@ -0,0 +1,5 @@
+#[test]
+#[available_gas(lots)]
+fn test_bad_attribute() {
+    assert(1 == 1, 'one is one');
+}
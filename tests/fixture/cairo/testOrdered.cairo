@@ -0,0 +1,19 @@
+#[test]
+fn test_a() {
+    assert(1 == 1, 'one is one');
+}
+
+#[test]
+fn test_b() {
+    assert(2 == 2, 'two is two');
+}
+
+#[test]
+fn test_c() {
+    assert(3 == 3, 'three is three');
+}
+
+#[test]
+fn test_d() {
+    assert(4 == 4, 'four is four');
+}
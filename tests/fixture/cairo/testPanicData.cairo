@@ -0,0 +1,5 @@
+#[test]
+#[should_panic(expected = ('not positive'))]
+fn test_panics_with_data() {
+    assert(0 > 1, 'not positive');
+}
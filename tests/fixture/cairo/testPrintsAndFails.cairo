@@ -0,0 +1,12 @@
+use debug::PrintTrait;
+
+#[test]
+fn test_prints_and_fails() {
+    'before failing'.print();
+    assert(0 == 1, 'zero is not one');
+}
+
+#[test]
+fn test_prints_and_passes() {
+    'still shown'.print();
+}
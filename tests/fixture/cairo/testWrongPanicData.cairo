@@ -0,0 +1,5 @@
+#[test]
+#[should_panic(expected = ('not negative'))]
+fn test_panics_with_other_data() {
+    assert(0 > 1, 'not positive');
+}
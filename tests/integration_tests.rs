@@ -1,8 +1,6 @@
 use assert_cmd::prelude::*;
-use glob::glob;
+use predicates::prelude::PredicateBooleanExt;
 
-use std::fs::File;
-use std::io::Read;
 use std::process::Command;
 
 #[test]
@@ -41,30 +39,6 @@ fn reset_no_exercise() {
         ));
 }
 
-#[test]
-fn all_exercises_require_confirmation() {
-    for exercise in glob("exercises/**/*.cairo").unwrap() {
-        let path = exercise.unwrap();
-        if path.file_name().unwrap() == "mod.cairo" {
-            continue;
-        }
-        let source = {
-            let mut file = File::open(&path).unwrap();
-            let mut s = String::new();
-            file.read_to_string(&mut s).unwrap();
-            s
-        };
-        source
-            .matches("// I AM NOT DONE")
-            .next()
-            .unwrap_or_else(|| {
-                panic!(
-                    "There should be an `I AM NOT DONE` annotation in {path:?}"
-                )
-            });
-    }
-}
-
 #[test]
 fn exercise_paths_should_be_in_exercise_dir() {
     let output = Command::cargo_bin("starklings")
@@ -188,3 +162,306 @@ fn run_cairo_single_test_failure() {
         .assert()
         .code(1);
 }
+
+#[test]
+fn run_cairo_single_test_failure_shows_message_and_location() {
+    Command::cargo_bin("starklings")
+        .unwrap()
+        .args(["run", "testFails"])
+        .current_dir("tests/fixture/cairo/")
+        .assert()
+        .code(1)
+        .stdout(predicates::str::contains("testFails.cairo:1:1"))
+        .stdout(predicates::str::contains("message: 0 should be equal to 1"));
+}
+
+#[test]
+fn run_cairo_single_test_failure_shows_printed_output() {
+    Command::cargo_bin("starklings")
+        .unwrap()
+        .args(["run", "testPrintsAndFails"])
+        .current_dir("tests/fixture/cairo/")
+        .assert()
+        .code(1)
+        .stdout(predicates::str::contains("printed:"))
+        .stdout(predicates::str::contains("before failing"));
+}
+
+#[test]
+fn run_cairo_single_test_success_shows_printed_output() {
+    Command::cargo_bin("starklings")
+        .unwrap()
+        .args(["run", "testPrintsAndFails"])
+        .current_dir("tests/fixture/cairo/")
+        .assert()
+        .code(1)
+        .stdout(predicates::str::contains("still shown"));
+}
+
+#[test]
+fn run_cairo_single_test_expected_panic_data_success() {
+    Command::cargo_bin("starklings")
+        .unwrap()
+        .args(["run", "testPanicData"])
+        .current_dir("tests/fixture/cairo/")
+        .assert()
+        .success();
+}
+
+#[test]
+fn run_cairo_single_test_wrong_panic_data_fails() {
+    Command::cargo_bin("starklings")
+        .unwrap()
+        .args(["run", "testWrongPanicData"])
+        .current_dir("tests/fixture/cairo/")
+        .assert()
+        .code(1)
+        .stdout(predicates::str::contains("but expected"));
+}
+
+#[test]
+fn run_cairo_single_test_malformed_attribute_fails() {
+    Command::cargo_bin("starklings")
+        .unwrap()
+        .args(["run", "testBadAttribute"])
+        .current_dir("tests/fixture/cairo/")
+        .assert()
+        .code(1)
+        .stdout(predicates::str::contains("testBadAttribute.cairo:2:1"));
+}
+
+#[test]
+fn run_cairo_single_starknet_success() {
+    Command::cargo_bin("starklings")
+        .unwrap()
+        .args(["run", "starknetPass"])
+        .current_dir("tests/fixture/cairo/")
+        .assert()
+        .success();
+}
+
+#[test]
+fn run_cairo_single_starknet_abi_mismatch_fails() {
+    Command::cargo_bin("starklings")
+        .unwrap()
+        .args(["run", "starknetWrongAbi"])
+        .current_dir("tests/fixture/cairo/")
+        .assert()
+        .code(1)
+        .stdout(predicates::str::contains("El ABI del contrato no coincide"));
+}
+
+#[test]
+fn verify_cairo_starknet_abi_success() {
+    Command::cargo_bin("starklings")
+        .unwrap()
+        .args(["verify"])
+        .current_dir("tests/fixture/starknet/")
+        .assert()
+        .success();
+}
+
+#[test]
+fn verify_cairo_output_mismatch_fails() {
+    Command::cargo_bin("starklings")
+        .unwrap()
+        .args(["verify"])
+        .current_dir("tests/fixture/output/")
+        .assert()
+        .code(1)
+        .stdout(predicates::str::contains(
+            "outputFail.cairo no es la esperada",
+        ));
+}
+
+#[test]
+fn run_cairo_single_output_checks_printed_felts() {
+    Command::cargo_bin("starklings")
+        .unwrap()
+        .args(["run", "outputPrints"])
+        .current_dir("tests/fixture/cairo/")
+        .assert()
+        .success();
+}
+
+#[test]
+fn run_cairo_single_output_wrong_printed_felts_fails() {
+    Command::cargo_bin("starklings")
+        .unwrap()
+        .args(["run", "outputWrongPrints"])
+        .current_dir("tests/fixture/cairo/")
+        .assert()
+        .code(1)
+        .stdout(predicates::str::contains("Valor impreso incorrecto"));
+}
+
+#[test]
+fn run_cairo_single_compile_panic_fails() {
+    Command::cargo_bin("starklings")
+        .unwrap()
+        .args(["run", "cairoPanics"])
+        .current_dir("tests/fixture/cairo/")
+        .assert()
+        .code(1);
+}
+
+#[test]
+fn run_cairo_single_compile_expected_panic_success() {
+    Command::cargo_bin("starklings")
+        .unwrap()
+        .args(["run", "cairoExpectedPanic"])
+        .current_dir("tests/fixture/cairo/")
+        .assert()
+        .success();
+}
+
+#[test]
+fn run_cairo_single_compile_prints_gas() {
+    Command::cargo_bin("starklings")
+        .unwrap()
+        .args(["run", "cairoPass"])
+        .current_dir("tests/fixture/cairo/")
+        .assert()
+        .success()
+        .stdout(predicates::str::contains("Gas consumido"));
+}
+
+#[test]
+fn run_cairo_single_compile_over_gas_budget_fails() {
+    Command::cargo_bin("starklings")
+        .unwrap()
+        .args(["run", "cairoGasBudget"])
+        .current_dir("tests/fixture/cairo/")
+        .assert()
+        .code(1)
+        .stdout(predicates::str::contains("el máximo permitido es 1"));
+}
+
+#[test]
+fn run_starklings_list_json() {
+    Command::cargo_bin("starklings")
+        .unwrap()
+        .args(["--format", "json", "list"])
+        .current_dir("tests/fixture/cairo/")
+        .assert()
+        .success()
+        .stdout(predicates::str::contains(
+            r#"{"name":"cairoPass","path":"compilePass.cairo","mode":"compile","state":"pending"}"#,
+        ));
+}
+
+#[test]
+fn run_cairo_single_test_failure_json() {
+    Command::cargo_bin("starklings")
+        .unwrap()
+        .args(["--format", "json", "run", "testFails"])
+        .current_dir("tests/fixture/cairo/")
+        .assert()
+        .code(1)
+        .stdout(predicates::str::contains(r#""success":false"#));
+}
+
+#[test]
+fn run_cairo_single_compile_failure_json_has_diagnostics() {
+    Command::cargo_bin("starklings")
+        .unwrap()
+        .args(["--format", "json", "run", "cairoFail"])
+        .current_dir("tests/fixture/cairo/")
+        .assert()
+        .code(1)
+        .stdout(predicates::str::contains(
+            r#""start":{"line":2,"column":5}"#,
+        ));
+}
+
+#[test]
+fn run_cairo_single_compile_failure_json_has_diagnostic_hints() {
+    Command::cargo_bin("starklings")
+        .unwrap()
+        .args(["--format", "json", "run", "cairoFail"])
+        .current_dir("tests/fixture/cairo/")
+        .assert()
+        .code(1)
+        .stdout(predicates::str::contains(
+            r#""hints":["Declare `hi` before calling it."]"#,
+        ));
+}
+
+#[test]
+fn run_cairo_tests_in_source_order() {
+    Command::cargo_bin("starklings-tester")
+        .unwrap()
+        .args(["--path", "testOrdered.cairo", "--jobs", "4"])
+        .current_dir("tests/fixture/cairo/")
+        .assert()
+        .success()
+        .stdout(predicates::str::is_match("(?s)test_a.*test_b.*test_c.*test_d").unwrap());
+}
+
+#[test]
+fn run_cairo_single_captures_printed_output() {
+    Command::cargo_bin("starklings")
+        .unwrap()
+        .args(["run", "cairoPrints"])
+        .current_dir("tests/fixture/cairo/")
+        .assert()
+        .success()
+        .stdout(predicates::str::contains("Salida del programa:\n  hola"))
+        .stdout(predicates::str::contains("[DEBUG]").not());
+}
+
+#[test]
+fn run_cairo_single_with_args_from_info() {
+    Command::cargo_bin("starklings")
+        .unwrap()
+        .args(["run", "cairoArgs"])
+        .current_dir("tests/fixture/cairo/")
+        .assert()
+        .success()
+        .stdout(predicates::str::contains("devolviendo [5]"));
+}
+
+#[test]
+fn run_cairo_single_with_args_from_cli() {
+    Command::cargo_bin("starklings")
+        .unwrap()
+        .args(["run", "cairoArgs", "--", "4", "5"])
+        .current_dir("tests/fixture/cairo/")
+        .assert()
+        .success()
+        .stdout(predicates::str::contains("devolviendo [9]"));
+}
+
+#[test]
+fn run_cairo_single_with_wrong_number_of_args_fails() {
+    Command::cargo_bin("starklings")
+        .unwrap()
+        .args(["run", "cairoArgs", "--", "4"])
+        .current_dir("tests/fixture/cairo/")
+        .assert()
+        .code(1)
+        .stdout(predicates::str::contains("expects 2 arguments"));
+}
+
+#[test]
+fn run_cairo_single_cases_success() {
+    Command::cargo_bin("starklings")
+        .unwrap()
+        .args(["run", "cairoCases"])
+        .current_dir("tests/fixture/cairo/")
+        .assert()
+        .success()
+        .stdout(predicates::str::contains("Los 2 casos"));
+}
+
+#[test]
+fn run_cairo_single_cases_reports_failed_cases() {
+    Command::cargo_bin("starklings")
+        .unwrap()
+        .args(["run", "cairoWrongCases"])
+        .current_dir("tests/fixture/cairo/")
+        .assert()
+        .code(1)
+        .stdout(predicates::str::contains("Fallan 1 de 2 casos"))
+        .stdout(predicates::str::contains("caso 2 (argumentos [3])"));
+}